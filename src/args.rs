@@ -0,0 +1,140 @@
+use std::path::Path;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+use crate::errors::file_error::FileError;
+use oxycards::{FileType, Reverse};
+
+#[derive(Parser, Debug)]
+#[command(author, version, about, subcommand_negates_reqs = true)]
+pub struct Args {
+    /// Path to a quiz md, csv, tsv, json or toml file
+    #[arg(short, long, required = true)]
+    pub path: Option<String>,
+
+    /// Draft answers on every card and grade them all at once when submitting
+    #[arg(short, long)]
+    pub review: bool,
+
+    /// Study flashcards back-to-front, showing the answer and hiding the question
+    #[arg(long, value_enum, num_args = 0..=1, default_missing_value = "always")]
+    pub reverse: Option<ReverseFlashcards>,
+
+    /// Only study cards due for review within their Leitner box
+    #[arg(short, long)]
+    pub leitner: bool,
+
+    /// Queue incorrectly answered cards up again until every card is answered correctly
+    #[arg(short, long, conflicts_with = "review")]
+    pub cram: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Which flashcards are studied back-to-front, as picked with `--reverse`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ReverseFlashcards {
+    /// Every flashcard
+    Always,
+    /// Each flashcard either way at random
+    Random,
+}
+
+impl From<ReverseFlashcards> for Reverse {
+    fn from(reverse: ReverseFlashcards) -> Self {
+        match reverse {
+            ReverseFlashcards::Always => Reverse::Always,
+            ReverseFlashcards::Random => Reverse::Random,
+        }
+    }
+}
+
+/// Formats a deck can be exported to.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ExportFormat {
+    /// Moodle's GIFT format
+    Gift,
+    /// A printable HTML page with a question sheet and answer key
+    Html,
+    /// A question sheet and answer key in Markdown, ready to be converted into a PDF
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Gift => "gift",
+            ExportFormat::Html => "html",
+            // Kept apart from the quiz md file being exported
+            ExportFormat::Markdown => "print.md",
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Print accuracy over time, weakest cards and streaks for each studied deck
+    Stats {
+        /// Only show statistics for this quiz md file
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+    /// Convert an Anki package (.apkg), Anki text export (.txt) or Moodle GIFT file (.gift) into a quiz md file
+    Import {
+        /// Path to the deck to import
+        input: String,
+        /// Where to write the quiz md file, defaults to the input with a .md extension
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Overwrite the output if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Show how many cards sit within each Leitner box for each studied deck
+    Boxes {
+        /// Only show boxes for this quiz md file
+        #[arg(short, long)]
+        path: Option<String>,
+    },
+    /// Convert a deck into another format, such as Moodle GIFT or a printable HTML page
+    Export {
+        /// Path to the deck
+        input: String,
+        /// Format to convert the deck into
+        #[arg(long, value_enum, default_value_t = ExportFormat::Gift)]
+        format: ExportFormat,
+        /// Where to write the converted deck, defaults to the input with the format's extension
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Overwrite the output if it already exists
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Rewrite quiz md files in a consistent format
+    Fmt {
+        /// Paths to the quiz md files
+        #[arg(required = true)]
+        paths: Vec<String>,
+        /// Report files that aren't formatted without changing them, exiting with an error if any
+        #[arg(long)]
+        check: bool,
+    },
+    /// Print the JSON Schema for decks written as JSON or TOML
+    Schema,
+}
+
+impl Args {
+    pub fn validate_file(file: &Path) -> Result<(), FileError> {
+        match file.extension() {
+            Some(extension) => {
+                if FileType::from_osstr(extension).is_some() {
+                    return Ok(());
+                }
+            }
+            None => return Err(FileError::InvalidFileType),
+        }
+
+        Err(FileError::InvalidFileType)
+    }
+}
@@ -0,0 +1,51 @@
+use std::error::Error;
+use std::path::Path;
+
+use chrono::Local;
+use crossterm::style::Stylize;
+
+use crate::history::History;
+use crate::leitner::{Leitner, BOXES};
+
+/// Print how many cards sit within each Leitner box for every deck, or only
+/// the deck at `path` if one is provided.
+pub fn run(path: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let deck = path.map(History::deck_key);
+    let today = Local::now().date_naive();
+    let leitner = Leitner::load()?;
+
+    let decks: Vec<_> = leitner
+        .decks
+        .iter()
+        .filter(|(key, _)| deck.as_ref().is_none_or(|deck| deck == *key))
+        .collect();
+
+    if decks.is_empty() {
+        println!("No cards have been boxed yet. Study a deck with --leitner to start.");
+        return Ok(());
+    }
+
+    for (deck, cards) in decks {
+        println!("{}", deck.as_str().bold());
+
+        for box_number in 1..=BOXES {
+            let boxed: Vec<_> = cards
+                .values()
+                .filter(|card| card.box_number == box_number)
+                .collect();
+            let due = boxed.iter().filter(|card| card.due() <= today).count();
+
+            println!(
+                "  Box {} (every {:>2} day(s)): {:>4} card(s), {:>4} due",
+                box_number,
+                Leitner::interval(box_number),
+                boxed.len(),
+                due
+            );
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::args::{Args, ExportFormat};
+use crate::errors::export_error::ExportError;
+use crate::errors::Errors;
+use crate::parse_cards;
+use oxycards::formats::structured::DeckCard;
+use oxycards::formats::{gift, printable};
+use oxycards::FileType;
+
+/// Convert a deck into another format, written to `output` or next to the
+/// input when no output is provided.
+pub fn run(
+    input: &Path,
+    format: ExportFormat,
+    output: Option<&Path>,
+    force: bool,
+) -> Result<(), Box<dyn Error>> {
+    if let Err(err) = Args::validate_file(input) {
+        Errors::throw_file_error(err)
+    };
+
+    let output = output.map_or_else(|| input.with_extension(format.extension()), PathBuf::from);
+
+    if output.exists() && !force {
+        Errors::throw_export_error(ExportError::OutputExists(output.display().to_string()));
+    }
+
+    let file_type = FileType::from_path(input).expect("Checked by validate_file");
+    let cards: Vec<DeckCard> = parse_cards(&fs::read_to_string(input)?, file_type)
+        .iter()
+        .map(|card| card.as_card_type().to_deck_card())
+        .collect();
+
+    let title = input
+        .file_stem()
+        .map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+
+    let (content, skipped) = match format {
+        ExportFormat::Gift => gift::write(&cards),
+        ExportFormat::Html => (printable::to_html(&title, &cards), 0),
+        ExportFormat::Markdown => (printable::to_markdown(&title, &cards), 0),
+    };
+
+    if skipped == cards.len() {
+        Errors::throw_export_error(ExportError::NoCards(format!("{:?}", format)));
+    }
+
+    fs::write(&output, content)?;
+    println!(
+        "Exported {} card(s) into {}",
+        cards.len() - skipped,
+        output.display()
+    );
+
+    if skipped > 0 {
+        println!(
+            "Skipped {} card(s) without a matching question type",
+            skipped
+        );
+    }
+
+    Ok(())
+}
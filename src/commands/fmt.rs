@@ -0,0 +1,67 @@
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+
+use crate::args::Args;
+use crate::errors::file_error::FileError;
+use crate::errors::Errors;
+use oxycards::{Card, FileType, ParsingError};
+
+/// Rewrite each quiz md file in the canonical format, or only report the files
+/// that aren't formatted when `check` is set.
+pub fn run(paths: &[String], check: bool) -> Result<(), Box<dyn Error>> {
+    let mut unformatted = 0;
+
+    for path in paths.iter().map(Path::new) {
+        if let Err(err) = Args::validate_file(path) {
+            Errors::throw_file_error(err)
+        };
+
+        if FileType::from_path(path) != Some(FileType::Markdown) {
+            Errors::throw_file_error(FileError::NotMarkdown(path.display().to_string()));
+        }
+
+        let content = fs::read_to_string(path)?;
+        let formatted = format(&content).unwrap_or_else(|err| Errors::throw_parsing_error(err));
+
+        if formatted == content {
+            continue;
+        }
+
+        unformatted += 1;
+
+        if check {
+            println!("{} is not formatted", path.display());
+        } else {
+            fs::write(path, formatted)?;
+            println!("Formatted {}", path.display());
+        }
+    }
+
+    if check && unformatted > 0 {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
+
+/// Write the deck in the canonical format.
+fn format(content: &str) -> Result<String, ParsingError> {
+    // Anything after a blank line within a card isn't read as part of the card,
+    // so would be lost by rewriting the deck
+    let ignored = content
+        .split("---")
+        .map(|section| section.trim().split("\n\n").filter(|item| !item.is_empty()))
+        .any(|mut items| items.nth(2).is_some());
+    if ignored {
+        return Err(ParsingError::IgnoredContent);
+    }
+
+    let deck_matching = Card::deck_matching(content)?;
+    let cards = Card::card_parser(content.to_string())?;
+
+    let deck = Card::write_deck(&cards, &deck_matching)?;
+    let formatted: Vec<&str> = deck.lines().map(str::trim_end).collect();
+
+    Ok(format!("{}\n", formatted.join("\n")))
+}
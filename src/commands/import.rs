@@ -0,0 +1,79 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::errors::Errors;
+use oxycards::formats::structured::{self, Deck};
+use oxycards::formats::{anki, gift};
+use oxycards::ImportError;
+
+/// Convert an Anki deck or Moodle GIFT file into an oxycards Markdown deck,
+/// written to `output` or next to the input when no output is provided.
+pub fn run(input: &Path, output: Option<&Path>, force: bool) -> Result<(), Box<dyn Error>> {
+    let output = output.map_or_else(|| input.with_extension("md"), PathBuf::from);
+
+    if output.exists() && !force {
+        Errors::throw_import_error(ImportError::OutputExists(output.display().to_string()));
+    }
+
+    let extension = input
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+
+    if extension.as_deref() == Some("gift") {
+        return import_gift(input, &output);
+    }
+
+    let notes = match extension.as_deref() {
+        Some("apkg" | "colpkg") => anki::read_package(input),
+        Some("txt" | "tsv") => fs::read_to_string(input)
+            .map_err(ImportError::from)
+            .and_then(|content| anki::read_text(&content)),
+        _ => Err(ImportError::UnsupportedFormat(input.display().to_string())),
+    }
+    .unwrap_or_else(|err| Errors::throw_import_error(err));
+
+    if notes.is_empty() {
+        Errors::throw_import_error(ImportError::NoNotes);
+    }
+
+    fs::write(&output, anki::to_markdown(&notes))?;
+    println!("Imported {} card(s) into {}", notes.len(), output.display());
+
+    Ok(())
+}
+
+fn import_gift(input: &Path, output: &Path) -> Result<(), Box<dyn Error>> {
+    let content = fs::read_to_string(input)
+        .map_err(ImportError::from)
+        .unwrap_or_else(|err| Errors::throw_import_error(err));
+
+    let (cards, skipped) = gift::read(&content);
+
+    if cards.is_empty() {
+        Errors::throw_import_error(ImportError::NoQuestions);
+    }
+
+    let deck = Deck {
+        options: Vec::new(),
+        cards,
+    };
+    let markdown =
+        structured::to_markdown(&deck).unwrap_or_else(|err| Errors::throw_parsing_error(err));
+
+    fs::write(output, format!("{}\n", markdown))?;
+    println!(
+        "Imported {} card(s) into {}",
+        deck.cards.len(),
+        output.display()
+    );
+
+    if skipped > 0 {
+        println!(
+            "Skipped {} question(s) without a matching card type",
+            skipped
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod boxes;
+pub mod export;
+pub mod fmt;
+pub mod import;
+pub mod stats;
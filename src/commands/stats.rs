@@ -0,0 +1,165 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::Path;
+
+use chrono::{Duration, Local, NaiveDate};
+use crossterm::style::Stylize;
+
+use crate::history::{History, SessionRecord};
+use oxycards::UserAnswer;
+
+/// Number of cards listed under the weakest cards section.
+const WEAKEST_CARDS: usize = 5;
+
+/// Print statistics for every deck held within the history store, or only the
+/// deck at `path` if one is provided.
+pub fn run(path: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let deck = path.map(History::deck_key);
+
+    let (records, skipped) = History::load()?;
+    if skipped > 0 {
+        eprintln!(
+            "{}",
+            format!(
+                "Skipped {} unreadable line(s) within {}",
+                skipped,
+                History::path()?.display()
+            )
+            .yellow()
+        );
+    }
+
+    let mut decks: BTreeMap<String, Vec<SessionRecord>> = BTreeMap::new();
+    for record in records {
+        if deck.as_ref().is_none_or(|deck| *deck == record.deck) {
+            decks.entry(record.deck.clone()).or_default().push(record);
+        }
+    }
+
+    if decks.is_empty() {
+        println!("No study history found. Finish a session to start tracking statistics.");
+        return Ok(());
+    }
+
+    for (deck, mut sessions) in decks {
+        sessions.sort_by_key(|session| session.date);
+        print_deck(&deck, &sessions);
+    }
+
+    Ok(())
+}
+
+fn print_deck(deck: &str, sessions: &[SessionRecord]) {
+    let (current, longest) = streaks(sessions);
+    let total_time: u64 = sessions.iter().map(|session| session.duration).sum();
+
+    println!("{}", deck.bold());
+    println!(
+        "  Sessions: {}, time studied: {}, current streak: {} day(s), longest streak: {} day(s)",
+        sessions.len(),
+        format_duration(total_time),
+        current,
+        longest
+    );
+
+    println!("  {}", "Accuracy over time".underlined());
+    for session in sessions {
+        let answered = session.correct() + session.incorrect();
+
+        println!(
+            "    {}  {:>3}/{:<3} {:>4}  {}",
+            session.date.format("%Y-%m-%d %H:%M"),
+            session.correct(),
+            answered,
+            format!("{}%", percentage(session.correct(), answered)),
+            format_duration(session.duration)
+        );
+    }
+
+    let weakest = weakest_cards(sessions);
+    if !weakest.is_empty() {
+        println!("  {}", "Weakest cards".underlined());
+        for (question, correct, incorrect) in weakest {
+            println!(
+                "    {:>4}  {} incorrect of {}  {}",
+                format!("{}%", percentage(correct, correct + incorrect)),
+                incorrect,
+                correct + incorrect,
+                question
+            );
+        }
+    }
+
+    println!();
+}
+
+/// Cards with at least one incorrect answer, ordered from lowest accuracy.
+fn weakest_cards(sessions: &[SessionRecord]) -> Vec<(String, usize, usize)> {
+    let mut cards: HashMap<&str, (usize, usize)> = HashMap::new();
+
+    for card in sessions.iter().flat_map(|session| session.cards.iter()) {
+        let (correct, incorrect) = cards.entry(&card.question).or_default();
+
+        match card.outcome {
+            UserAnswer::Correct => *correct += 1,
+            UserAnswer::Incorrect => *incorrect += 1,
+            UserAnswer::Undecided => {}
+        }
+    }
+
+    let mut weakest: Vec<(String, usize, usize)> = cards
+        .into_iter()
+        .filter(|(_, (_, incorrect))| *incorrect > 0)
+        .map(|(question, (correct, incorrect))| (question.to_string(), correct, incorrect))
+        .collect();
+
+    weakest.sort_by(|a, b| {
+        (a.1 * (b.1 + b.2))
+            .cmp(&(b.1 * (a.1 + a.2)))
+            .then(b.2.cmp(&a.2))
+            .then(a.0.cmp(&b.0))
+    });
+    weakest.truncate(WEAKEST_CARDS);
+
+    weakest
+}
+
+/// Return the current and longest run of consecutive days studied.
+fn streaks(sessions: &[SessionRecord]) -> (usize, usize) {
+    let mut days: Vec<NaiveDate> = sessions
+        .iter()
+        .map(|session| session.date.date_naive())
+        .collect();
+    days.sort();
+    days.dedup();
+
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+
+    for day in days.iter() {
+        run = match previous {
+            Some(previous) if *day - previous == Duration::days(1) => run + 1,
+            _ => 1,
+        };
+        longest = longest.max(run);
+        previous = Some(*day);
+    }
+
+    // A streak is only current if the deck was studied today or yesterday
+    let today = Local::now().date_naive();
+    let current = match days.last() {
+        Some(last) if today - *last <= Duration::days(1) => run,
+        _ => 0,
+    };
+
+    (current, longest)
+}
+
+fn percentage(part: usize, total: usize) -> usize {
+    (part * 100).checked_div(total).unwrap_or(0)
+}
+
+fn format_duration(seconds: u64) -> String {
+    format!("{}m {:02}s", seconds / 60, seconds % 60)
+}
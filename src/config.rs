@@ -0,0 +1,42 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use oxycards::{KeyBindings, Theme};
+
+use crate::{errors::config_error::ConfigError, paths};
+
+/// User settings, read from `config.toml` within the users config directory.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub keys: KeyBindings,
+    pub theme: Theme,
+}
+
+impl Config {
+    pub fn path() -> Result<PathBuf, io::Error> {
+        Ok(paths::config_dir()?.join("config.toml"))
+    }
+
+    /// Load the users config, falling back to the defaults if there isn't one.
+    pub fn load() -> Result<Self, ConfigError> {
+        let content = match Config::path().and_then(fs::read_to_string) {
+            Ok(content) => content,
+            // Without a config file every setting uses its default
+            Err(err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(ConfigError::Read(err.to_string())),
+        };
+
+        let mut config: Config =
+            toml::from_str(&content).map_err(|err| ConfigError::Parse(err.to_string()))?;
+
+        if Theme::no_color() {
+            config.theme = Theme::monochrome();
+        }
+
+        Ok(config)
+    }
+}
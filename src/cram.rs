@@ -0,0 +1,39 @@
+use rand::Rng;
+
+use oxycards::UserAnswer;
+
+/// Tracks the attempts taken on each card while cramming, where incorrectly
+/// answered cards are queued up again until answered correctly.
+pub struct Cram {
+    /// Index of the deck card each card within the queue is an attempt at
+    pub origins: Vec<usize>,
+    /// Number of attempts taken on each card of the deck
+    pub attempts: Vec<usize>,
+}
+
+impl Cram {
+    pub fn new(cards: usize) -> Self {
+        Self {
+            origins: (0..cards).collect(),
+            attempts: vec![0; cards],
+        }
+    }
+
+    /// Record the answer given to the card at `index` within the queue.
+    ///
+    /// Returns where another attempt at the card should be queued, a few
+    /// cards later, if it was answered incorrectly.
+    pub fn record(&mut self, index: usize, answer: UserAnswer, queued: usize) -> Option<usize> {
+        let origin = self.origins[index];
+        self.attempts[origin] += 1;
+
+        if answer != UserAnswer::Incorrect {
+            return None;
+        }
+
+        let position = (index + 1 + rand::thread_rng().gen_range(2..=4)).min(queued);
+        self.origins.insert(position, origin);
+
+        Some(position)
+    }
+}
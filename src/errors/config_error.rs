@@ -0,0 +1,18 @@
+use core::fmt;
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(String),
+    Parse(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read(err) => write!(f, "Unable to read config file: {}", err),
+            ConfigError::Parse(err) => write!(f, "Invalid config file: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -0,0 +1,22 @@
+use core::fmt;
+
+#[derive(Debug)]
+pub enum ExportError {
+    OutputExists(String),
+    NoCards(String),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::OutputExists(path) => {
+                write!(f, "{} already exists. Use --force to overwrite it", path)
+            }
+            ExportError::NoCards(format) => {
+                write!(f, "None of the deck's cards can be written as {}", format)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
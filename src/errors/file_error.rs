@@ -0,0 +1,24 @@
+use core::fmt;
+
+#[derive(Debug)]
+pub enum FileError {
+    InvalidFileType,
+    NotMarkdown(String),
+}
+
+impl fmt::Display for FileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FileError::InvalidFileType => write!(f, "Invalid file type"),
+            FileError::NotMarkdown(path) => {
+                write!(
+                    f,
+                    "{} is not a quiz md file, only md decks can be formatted",
+                    path
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for FileError {}
@@ -0,0 +1,56 @@
+use crossterm::style::Stylize;
+
+use oxycards::{ImportError, ParsingError, PluginError};
+
+use crate::reset_terminal;
+
+pub mod config_error;
+pub mod export_error;
+pub mod file_error;
+
+use config_error::ConfigError;
+use export_error::ExportError;
+use file_error::FileError;
+
+pub enum Errors {
+    ParsingError(ParsingError),
+    FileError(FileError),
+    ConfigError(ConfigError),
+    ImportError(ImportError),
+    ExportError(ExportError),
+    PluginError(PluginError),
+}
+
+impl Errors {
+    fn throw_error(err_type: &str, err: String) -> ! {
+        eprintln!("{}: {}", format!("{} Error", err_type).red().bold(), err);
+        reset_terminal().unwrap();
+        std::process::exit(1);
+    }
+
+    pub fn throw_parsing_error(err: ParsingError) -> ! {
+        Errors::throw_error("Parsing", err.to_string())
+    }
+
+    pub fn throw_file_error(err: FileError) -> ! {
+        Errors::throw_error("File", err.to_string())
+    }
+
+    pub fn throw_config_error(err: ConfigError) -> ! {
+        Errors::throw_error("Config", err.to_string())
+    }
+
+    pub fn throw_import_error(err: ImportError) -> ! {
+        Errors::throw_error("Import", err.to_string())
+    }
+
+    pub fn throw_export_error(err: ExportError) -> ! {
+        Errors::throw_error("Export", err.to_string())
+    }
+
+    /// Report a plugin that couldn't be loaded, without exiting as the rest of
+    /// the application still works.
+    pub fn warn_plugin_error(err: PluginError) {
+        eprintln!("{}: {}", "Plugin Warning".yellow().bold(), err);
+    }
+}
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use regex::Regex;
+use rusqlite::Connection;
+use serde::Deserialize;
+
+use super::{blank_answer, escape_html, html_to_text, paragraph, single_line};
+use crate::models::errors::import_error::ImportError;
+
+/// A note that can be brought over from Anki.
+#[derive(Debug, PartialEq)]
+pub enum AnkiNote {
+    /// A note with a front and a back, e.g. the Basic note type
+    Basic {
+        front: String,
+        back: String,
+        /// Notes such as `Basic (and reversed card)` are studied both ways
+        reversible: bool,
+    },
+    /// A note with its text hidden behind `{{c1::...}}` deletions
+    Cloze { text: String },
+}
+
+/// The parts of an Anki note type needed to tell notes apart.
+#[derive(Deserialize)]
+struct NoteType {
+    name: String,
+    /// `1` for cloze note types
+    #[serde(rename = "type", default)]
+    kind: u8,
+}
+
+impl AnkiNote {
+    /// Create a note from its fields, returning `None` for notes that can't be
+    /// represented as a card.
+    fn from_fields(fields: &[String], note_type: Option<&str>, cloze: bool) -> Option<Self> {
+        let first = fields.first().map(|field| html_to_text(field))?;
+        let deletions = cloze_regex().is_match(&first);
+
+        // A cloze note without any deletions would have nothing to fill in, and
+        // `_` anywhere within its text would mark the start of another blank
+        if cloze || deletions {
+            return (deletions && blank_answer(&first).is_some())
+                .then_some(AnkiNote::Cloze { text: first });
+        }
+
+        let back = fields.get(1).map(|field| html_to_text(field))?;
+        if first.is_empty() || back.is_empty() {
+            return None;
+        }
+
+        Some(AnkiNote::Basic {
+            front: first,
+            back,
+            reversible: note_type.is_some_and(|name| name.to_lowercase().contains("reverse")),
+        })
+    }
+}
+
+/// Read the notes held within an Anki package (`.apkg` or `.colpkg`).
+pub fn read_package(path: &Path) -> Result<Vec<AnkiNote>, ImportError> {
+    let file = File::open(path).map_err(|err| ImportError::Read(err.to_string()))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|err| ImportError::Package(err.to_string()))?;
+
+    // Newer collections are stored alongside a legacy one for older versions
+    let name = ["collection.anki21", "collection.anki2"]
+        .into_iter()
+        .find(|name| archive.by_name(name).is_ok())
+        .ok_or_else(|| {
+            ImportError::Package(String::from(
+                "no collection found, export the deck with \"Support older Anki versions\" enabled",
+            ))
+        })?;
+
+    let mut collection = Vec::new();
+    archive
+        .by_name(name)
+        .and_then(|mut entry| Ok(entry.read_to_end(&mut collection)?))
+        .map_err(|err| ImportError::Package(err.to_string()))?;
+
+    // SQLite needs a file to open, so the collection is copied out of the
+    // archive into a new file only this process can access, removed once dropped
+    let mut database = tempfile::Builder::new()
+        .prefix("oxycards-")
+        .suffix(".anki2")
+        .tempfile()
+        .map_err(|err| ImportError::Read(err.to_string()))?;
+    database
+        .write_all(&collection)
+        .map_err(|err| ImportError::Read(err.to_string()))?;
+
+    read_collection(database.path()).map_err(|err| ImportError::Package(err.to_string()))
+}
+
+fn read_collection(path: &Path) -> Result<Vec<AnkiNote>, rusqlite::Error> {
+    let connection = Connection::open(path)?;
+
+    let models: String = connection.query_row("SELECT models FROM col", [], |row| row.get(0))?;
+    let note_types: HashMap<String, NoteType> = serde_json::from_str(&models).unwrap_or_default();
+
+    let mut statement = connection.prepare("SELECT mid, flds FROM notes ORDER BY id")?;
+    let rows = statement.query_map([], |row| {
+        Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut notes = Vec::new();
+    for row in rows {
+        let (model, fields) = row?;
+        let fields: Vec<String> = fields.split('\x1f').map(String::from).collect();
+        let note_type = note_types.get(&model.to_string());
+
+        if let Some(note) = AnkiNote::from_fields(
+            &fields,
+            note_type.map(|note_type| note_type.name.as_str()),
+            note_type.is_some_and(|note_type| note_type.kind == 1),
+        ) {
+            notes.push(note);
+        }
+    }
+
+    Ok(notes)
+}
+
+/// Read the notes held within Anki's "Notes in Plain Text" export.
+pub fn read_text(content: &str) -> Result<Vec<AnkiNote>, ImportError> {
+    let mut separator = b'\t';
+    let mut html = true;
+    // Columns holding information other than the notes fields, counted from 1
+    let mut note_type_column = None;
+    let mut skipped_columns = Vec::new();
+
+    // The export starts with headers such as `#separator:tab`
+    let mut lines = content.lines().peekable();
+    while let Some((key, value)) = lines
+        .peek()
+        .and_then(|line| line.strip_prefix('#'))
+        .and_then(|line| line.split_once(':'))
+    {
+        match key {
+            "separator" => {
+                separator = match value.to_lowercase().as_str() {
+                    "tab" => b'\t',
+                    "comma" => b',',
+                    "semicolon" => b';',
+                    "space" => b' ',
+                    "pipe" => b'|',
+                    "colon" => b':',
+                    other => other.bytes().next().unwrap_or(b'\t'),
+                }
+            }
+            "html" => html = value == "true",
+            "notetype column" => note_type_column = value.parse::<usize>().ok(),
+            "deck column" | "tags column" | "guid column" => {
+                skipped_columns.extend(value.parse::<usize>().ok())
+            }
+            _ => {}
+        }
+
+        lines.next();
+    }
+
+    let body = lines.collect::<Vec<&str>>().join("\n");
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(separator)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(body.as_bytes());
+
+    let mut notes = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|err| ImportError::Read(err.to_string()))?;
+
+        let note_type = note_type_column.and_then(|column| record.get(column - 1));
+        let fields: Vec<String> = record
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| Some(i + 1) != note_type_column && !skipped_columns.contains(&(i + 1)))
+            .map(|(_, field)| {
+                if html {
+                    field.to_string()
+                } else {
+                    escape_html(field)
+                }
+            })
+            .collect();
+
+        let cloze = note_type.is_some_and(|name| name.to_lowercase().contains("cloze"));
+        if let Some(note) = AnkiNote::from_fields(&fields, note_type, cloze) {
+            notes.push(note);
+        }
+    }
+
+    Ok(notes)
+}
+
+/// Write the notes as an oxycards Markdown deck.
+pub fn to_markdown(notes: &[AnkiNote]) -> String {
+    let cards: Vec<String> = notes
+        .iter()
+        .map(|note| match note {
+            AnkiNote::Basic {
+                front,
+                back,
+                reversible,
+            } => format!(
+                "flashcard{}\n\n# {}\n{}",
+                if *reversible { " reversible" } else { "" },
+                single_line(front),
+                paragraph(back)
+            ),
+            AnkiNote::Cloze { text } => format!(
+                "fill_in_the_blanks\n\n# Fill in the gaps\n{}",
+                paragraph(
+                    &cloze_regex().replace_all(text, |deletion: &regex::Captures| {
+                        format!("_{}_", blank_answer(&deletion[1]).unwrap_or_default())
+                    })
+                )
+            ),
+        })
+        .collect();
+
+    format!("{}\n", cards.join("\n\n---\n\n"))
+}
+
+/// Matches a cloze deletion e.g. `{{c1::answer}}` or `{{c1::answer::hint}}`.
+fn cloze_regex() -> Regex {
+    Regex::new(r"\{\{c\d+::(.*?)(?:::.*?)?\}\}").expect("Error with regex string.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_deck, Card, FileType};
+
+    fn fields(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|field| field.to_string()).collect()
+    }
+
+    fn basic(front: &str, back: &str, reversible: bool) -> AnkiNote {
+        AnkiNote::Basic {
+            front: front.to_string(),
+            back: back.to_string(),
+            reversible,
+        }
+    }
+
+    #[test]
+    fn basic_notes() {
+        let note = AnkiNote::from_fields(
+            &fields(&["Capital of <b>France</b>?", "Paris<br>on the Seine"]),
+            Some("Basic"),
+            false,
+        );
+
+        assert_eq!(
+            note,
+            Some(basic("Capital of France?", "Paris\non the Seine", false))
+        );
+    }
+
+    #[test]
+    fn reversed_notes() {
+        let note = AnkiNote::from_fields(
+            &fields(&["chat", "cat"]),
+            Some("Basic (and reversed card)"),
+            false,
+        );
+
+        assert_eq!(note, Some(basic("chat", "cat", true)));
+    }
+
+    #[test]
+    fn notes_missing_a_side_are_skipped() {
+        assert_eq!(
+            AnkiNote::from_fields(&fields(&["chat", ""]), Some("Basic"), false),
+            None
+        );
+        assert_eq!(
+            AnkiNote::from_fields(&fields(&["chat"]), Some("Basic"), false),
+            None
+        );
+    }
+
+    #[test]
+    fn cloze_notes() {
+        let text = "{{c1::Canberra::city}} is the capital of {{c2::Australia}}";
+
+        assert_eq!(
+            AnkiNote::from_fields(&fields(&[text, "Extra"]), Some("Cloze"), true),
+            Some(AnkiNote::Cloze {
+                text: text.to_string()
+            })
+        );
+
+        // Deletions are found without the note type too
+        assert_eq!(
+            AnkiNote::from_fields(&fields(&[text]), None, false),
+            Some(AnkiNote::Cloze {
+                text: text.to_string()
+            })
+        );
+
+        assert_eq!(
+            AnkiNote::from_fields(&fields(&["No deletions"]), Some("Cloze"), true),
+            None
+        );
+    }
+
+    #[test]
+    fn cloze_notes_holding_underscores_are_skipped() {
+        for text in [
+            "{{c1::snake_case}} names",
+            "In snake_case, {{c1::underscores}} separate words",
+        ] {
+            assert_eq!(
+                AnkiNote::from_fields(&fields(&[text]), Some("Cloze"), true),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn text_export() {
+        let content = "#separator:tab\n\
+            #html:false\n\
+            #notetype column:1\n\
+            #tags column:4\n\
+            Basic\tchat\tcat\tfrench\n\
+            Basic (and reversed card)\t1 < 2\ttrue\t\n\
+            Cloze\t{{c1::Paris}} is in France\t\t\n";
+
+        let notes = read_text(content).unwrap();
+
+        assert_eq!(
+            notes,
+            [
+                basic("chat", "cat", false),
+                basic("1 < 2", "true", true),
+                AnkiNote::Cloze {
+                    text: String::from("{{c1::Paris}} is in France")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn notes_are_written_as_a_deck() {
+        let notes = [
+            basic("chat", "cat\n\n---\nfeline", true),
+            AnkiNote::Cloze {
+                text: String::from("{{c1::Paris|Lyon}} and {{c2::Berlin::city}}"),
+            },
+        ];
+
+        let cards = parse_deck(&to_markdown(&notes), FileType::Markdown).unwrap();
+
+        let Card::FlashCard(flashcard) = &cards[0] else {
+            panic!("expected a flashcard");
+        };
+        assert_eq!(flashcard.question, "chat");
+        assert_eq!(flashcard.answer, "cat\n—\nfeline");
+        assert!(flashcard.reversible);
+
+        let Card::FillInTheBlanks(blanks) = &cards[1] else {
+            panic!("expected a fill in the blanks card");
+        };
+        assert_eq!(blanks.raw_content(), "_Paris/Lyon_ and _Berlin_");
+    }
+}
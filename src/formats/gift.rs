@@ -0,0 +1,666 @@
+use regex::Regex;
+
+use super::structured::DeckCard;
+use super::{blank_answer, html_to_text};
+use crate::models::matching::numeric_range;
+
+/// Characters with a meaning within GIFT, escaped with a backslash to be used
+/// as text.
+const SPECIAL: [char; 6] = ['~', '=', '#', '{', '}', ':'];
+
+/// Read the questions within a Moodle GIFT file, returning the cards along
+/// with how many questions couldn't be represented as a card, e.g. essays or
+/// matching questions.
+pub fn read(content: &str) -> (Vec<DeckCard>, usize) {
+    let mut cards = Vec::new();
+    let mut skipped = 0;
+
+    for block in question_blocks(content) {
+        match read_question(&block) {
+            Some(card) => cards.push(card),
+            None => skipped += 1,
+        }
+    }
+
+    (cards, skipped)
+}
+
+/// Split the file into questions, which are separated by blank lines.
+fn question_blocks(content: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut block: Vec<&str> = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            if !block.is_empty() {
+                blocks.push(block.join("\n"));
+                block.clear();
+            }
+        } else if !trimmed.starts_with("//") && !trimmed.starts_with("$CATEGORY:") {
+            block.push(line);
+        }
+    }
+
+    if !block.is_empty() {
+        blocks.push(block.join("\n"));
+    }
+
+    blocks
+}
+
+fn read_question(block: &str) -> Option<DeckCard> {
+    let mut text = block.trim();
+
+    // An optional title e.g. `::Capitals::`
+    let mut title = None;
+    if let Some(rest) = text.strip_prefix("::") {
+        let end = find_unescaped(rest, "::")?;
+        title = Some(unescape(&rest[..end]).trim().to_string());
+        text = rest[end + 2..].trim_start();
+    }
+
+    // An optional format e.g. `[html]`, which Moodle adds to every question
+    let mut html = false;
+    if let Some(rest) = text.strip_prefix('[') {
+        if let Some((format, rest)) = rest.split_once(']') {
+            html = format.eq_ignore_ascii_case("html");
+            text = rest;
+        }
+    }
+
+    // Questions without answers are descriptions rather than questions
+    let open = find_unescaped(text, "{")?;
+    let close = open + 1 + find_unescaped(&text[open + 1..], "}")?;
+
+    let to_text = |text: &str| {
+        let text = unescape(text);
+        if html {
+            html_to_text(&text)
+        } else {
+            text.trim().to_string()
+        }
+    };
+    let before = to_text(&text[..open]);
+    let after = to_text(&text[close + 1..]);
+    let answer = text[open + 1..close].trim();
+
+    // The answer can sit in the middle of the text e.g. `The {=blue} sky`
+    let question = |blank: &str| {
+        let question = if after.is_empty() {
+            before.clone()
+        } else {
+            format!("{} {} {}", before, blank, after)
+        };
+
+        match (question.trim().is_empty(), &title) {
+            (true, Some(title)) => title.clone(),
+            _ => question.trim().to_string(),
+        }
+    };
+
+    // Essays have no answer
+    if answer.is_empty() {
+        return None;
+    }
+
+    if let Some(numbers) = answer.strip_prefix('#') {
+        let answers: Vec<String> = split_answers(numbers)
+            .into_iter()
+            .filter(Answer::full_marks)
+            .map(|answer| format!("#{}", answer.text.replace(' ', "")))
+            .filter(|answer| numeric_range(answer).is_some())
+            .collect();
+
+        if answers.is_empty() {
+            return None;
+        }
+
+        return Some(DeckCard::FillInTheBlanks {
+            question: question("_____"),
+            content: format!("_{}_", answers.join("|")),
+            options: vec![String::from("numeric")],
+        });
+    }
+
+    // True/false answers may be followed by feedback e.g. `{T#Correct}`
+    let boolean = answer.split('#').next().unwrap_or_default().trim();
+    let correct = match boolean.to_uppercase().as_str() {
+        "T" | "TRUE" => Some("True"),
+        "F" | "FALSE" => Some("False"),
+        _ => None,
+    };
+    if let Some(correct) = correct {
+        return Some(DeckCard::MultipleChoice {
+            question: question("_____"),
+            choices: vec![String::from("True"), String::from("False")],
+            answers: vec![String::from(correct)],
+        });
+    }
+
+    let answers = split_answers(answer);
+
+    // Matching questions pair answers with `->`
+    if answers.is_empty() || answers.iter().any(|answer| answer.matching) {
+        return None;
+    }
+
+    let choices: Vec<String> = answers.iter().map(|answer| to_text(&answer.text)).collect();
+
+    if answers.iter().all(|answer| answer.correct) {
+        // Every answer is accepted, so the question is a short answer
+        let accepted: Vec<String> = answers
+            .iter()
+            .zip(&choices)
+            .filter(|(answer, _)| answer.full_marks())
+            .map(|(_, choice)| blank_answer(choice))
+            .collect::<Option<_>>()?;
+        let blank = format!("_{}_", accepted.join("|"));
+
+        // Text around the blank is held within the same content
+        if !after.is_empty() && (before.contains('_') || after.contains('_')) {
+            return None;
+        }
+
+        return Some(if after.is_empty() {
+            DeckCard::FillInTheBlanks {
+                question: question(""),
+                content: blank,
+                options: Vec::new(),
+            }
+        } else {
+            DeckCard::FillInTheBlanks {
+                question: title.unwrap_or_else(|| String::from("Fill in the gaps")),
+                content: format!("{} {} {}", before, blank, after).trim().to_string(),
+                options: Vec::new(),
+            }
+        });
+    }
+
+    if answers.iter().any(|answer| answer.correct) {
+        let correct: Vec<String> = answers
+            .iter()
+            .zip(&choices)
+            .filter(|(answer, _)| answer.correct)
+            .map(|(_, choice)| choice.clone())
+            .collect();
+
+        // A multiple choice card has a single answer, so questions with more
+        // than one are answered by selecting each of them
+        return Some(if correct.len() > 1 {
+            DeckCard::MultipleAnswer {
+                question: question("_____"),
+                choices,
+                answers: correct,
+            }
+        } else {
+            DeckCard::MultipleChoice {
+                question: question("_____"),
+                choices,
+                answers: correct,
+            }
+        });
+    }
+
+    // Only weighted answers, where any with a positive weight is correct
+    let correct: Vec<String> = answers
+        .iter()
+        .zip(&choices)
+        .filter(|(answer, _)| answer.weight.is_some_and(|weight| weight > 0.0))
+        .map(|(_, choice)| choice.clone())
+        .collect();
+
+    if correct.is_empty() {
+        return None;
+    }
+
+    Some(DeckCard::MultipleAnswer {
+        question: question("_____"),
+        choices,
+        answers: correct,
+    })
+}
+
+/// An answer within a question's braces.
+struct Answer {
+    /// Marked by `=` rather than `~`
+    correct: bool,
+    /// A percentage e.g. `%50%`
+    weight: Option<f64>,
+    /// The answer without its weight or feedback, still escaped
+    text: String,
+    /// Pairs two answers with `->`, as matching questions do
+    matching: bool,
+}
+
+impl Answer {
+    /// Answers given partial credit aren't accepted, as cards are either right
+    /// or wrong.
+    fn full_marks(&self) -> bool {
+        self.weight.is_none_or(|weight| weight >= 100.0)
+    }
+}
+
+/// Split the answers within a question's braces, each starting with `=` or `~`.
+fn split_answers(answers: &str) -> Vec<Answer> {
+    let mut starts = Vec::new();
+    let mut escaped = false;
+
+    for (i, c) in answers.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '=' | '~' => starts.push(i),
+            _ => {}
+        }
+    }
+
+    // A single numeric answer has no marker
+    if starts.first() != Some(&0) {
+        starts.insert(0, 0);
+    }
+
+    starts
+        .iter()
+        .enumerate()
+        .map(|(index, &start)| {
+            let end = starts.get(index + 1).copied().unwrap_or(answers.len());
+            let answer = &answers[start..end];
+
+            let correct = !answer.starts_with('~');
+            let mut text = answer.trim_start_matches(['=', '~']).trim();
+
+            let mut weight = None;
+            if let Some(rest) = text.strip_prefix('%') {
+                if let Some((value, rest)) = rest.split_once('%') {
+                    weight = value.trim().parse::<f64>().ok();
+                    text = rest;
+                }
+            }
+
+            // Feedback follows the answer e.g. `=Paris#Well done`
+            if let Some(feedback) = find_unescaped(text, "#") {
+                text = &text[..feedback];
+            }
+
+            Answer {
+                correct,
+                weight,
+                matching: find_unescaped(text, "->").is_some(),
+                text: text.trim().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Find a pattern that isn't escaped by a backslash.
+fn find_unescaped(text: &str, pattern: &str) -> Option<usize> {
+    let mut escaped = false;
+
+    for (i, c) in text.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if c == '\\' {
+            escaped = true;
+        } else if text[i..].starts_with(pattern) {
+            return Some(i);
+        }
+    }
+
+    None
+}
+
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some(next)) if SPECIAL.contains(&next) || next == '\\' => {
+                unescaped.push(next);
+                chars.next();
+            }
+            _ => unescaped.push(c),
+        }
+    }
+
+    unescaped
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+
+    for c in text.chars() {
+        match c {
+            '\n' => escaped.push_str("\\n"),
+            c if SPECIAL.contains(&c) || c == '\\' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Write the cards as Moodle GIFT questions, returning the file along with how
+/// many cards have no GIFT equivalent, i.e. flashcards and order cards.
+pub fn write(cards: &[DeckCard]) -> (String, usize) {
+    let mut questions = Vec::new();
+    let mut skipped = 0;
+
+    for card in cards {
+        let written = write_card(card);
+
+        if written.is_empty() {
+            skipped += 1;
+        }
+
+        questions.extend(written);
+    }
+
+    (format!("{}\n", questions.join("\n\n")), skipped)
+}
+
+fn write_card(card: &DeckCard) -> Vec<String> {
+    match card {
+        DeckCard::MultipleChoice {
+            question,
+            choices,
+            answers,
+        } => {
+            // Cards with only a true and a false choice are true/false questions
+            let boolean =
+                |choice: &String| ["true", "false"].contains(&choice.to_lowercase().as_str());
+            if choices.len() == 2 && answers.len() == 1 && choices.iter().all(boolean) {
+                let answer = if answers[0].eq_ignore_ascii_case("true") {
+                    "T"
+                } else {
+                    "F"
+                };
+
+                return vec![format!("{} {{{}}}", escape(question), answer)];
+            }
+
+            let choices: Vec<String> = choices
+                .iter()
+                .map(|choice| {
+                    let marker = if answers.contains(choice) { '=' } else { '~' };
+                    format!("\t{}{}", marker, escape(choice))
+                })
+                .collect();
+
+            vec![format!(
+                "{} {{\n{}\n}}",
+                escape(question),
+                choices.join("\n")
+            )]
+        }
+        DeckCard::MultipleAnswer {
+            question,
+            choices,
+            answers,
+        } => {
+            if answers.is_empty() {
+                return Vec::new();
+            }
+
+            // The weights of the correct choices add up to 100%
+            let weight = format_weight(100.0 / answers.len() as f64);
+            let choices: Vec<String> = choices
+                .iter()
+                .map(|choice| {
+                    let weight = if answers.contains(choice) {
+                        weight.as_str()
+                    } else {
+                        "-100"
+                    };
+                    format!("\t~%{}%{}", weight, escape(choice))
+                })
+                .collect();
+
+            vec![format!(
+                "{} {{\n{}\n}}",
+                escape(question),
+                choices.join("\n")
+            )]
+        }
+        DeckCard::FillInTheBlanks {
+            question,
+            content,
+            options,
+        } => write_blanks(question, content, options.iter().any(|o| o == "numeric")),
+        DeckCard::FlashCard { .. } | DeckCard::Order { .. } | DeckCard::Plugin { .. } => Vec::new(),
+    }
+}
+
+/// GIFT questions hold a single blank, so a question is written for each blank
+/// with the other blanks filled in.
+fn write_blanks(question: &str, content: &str, numeric: bool) -> Vec<String> {
+    let re = Regex::new(r"_(.*?)_").expect("Error with regex string.");
+    let blanks: Vec<Vec<&str>> = re
+        .captures_iter(content)
+        .map(|c| c.get(1).map_or("", |m| m.as_str()).split('|').collect())
+        .collect();
+    let parts: Vec<&str> = re.split(content).collect();
+
+    let mut questions = Vec::new();
+    for (index, answers) in blanks.iter().enumerate() {
+        let Some(answer) = write_blank(answers, numeric) else {
+            continue;
+        };
+
+        let mut text = String::new();
+        for (i, part) in parts.iter().enumerate() {
+            text.push_str(&escape(part));
+
+            if i == index {
+                text.push_str(&answer);
+            } else if let Some(filled) = blanks.get(i) {
+                text.push_str(&escape(filled.first().copied().unwrap_or_default()));
+            }
+        }
+
+        questions.push(format!("::{}::{}", escape(question), text.trim()));
+    }
+
+    questions
+}
+
+/// Write a blank's answers, with numeric answers as a numeric question. Regex
+/// answers have no GIFT equivalent, so are left out.
+fn write_blank(answers: &[&str], numeric: bool) -> Option<String> {
+    let is_number = |answer: &&str| numeric && numeric_range(answer).is_some();
+
+    if answers.iter().all(is_number) {
+        let numbers: Vec<&str> = answers.iter().map(|answer| &answer[1..]).collect();
+
+        return Some(match numbers.as_slice() {
+            [number] => format!("{{#{}}}", number),
+            numbers => format!("{{#={}}}", numbers.join(" =")),
+        });
+    }
+
+    let answers: Vec<String> = answers
+        .iter()
+        .filter(|answer| !(answer.len() > 2 && answer.starts_with('/') && answer.ends_with('/')))
+        .filter(|answer| !is_number(answer))
+        .map(|answer| format!("={}", escape(answer)))
+        .collect();
+
+    if answers.is_empty() {
+        return None;
+    }
+
+    Some(format!("{{{}}}", answers.join(" ")))
+}
+
+/// Weights are written with at most five decimal places, as Moodle expects.
+fn format_weight(weight: f64) -> String {
+    let weight = format!("{:.5}", weight);
+    weight
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    fn blanks(question: &str, content: &str, options: &[&str]) -> DeckCard {
+        DeckCard::FillInTheBlanks {
+            question: question.to_string(),
+            content: content.to_string(),
+            options: strings(options),
+        }
+    }
+
+    fn read_one(gift: &str) -> DeckCard {
+        let (mut cards, skipped) = read(gift);
+        assert_eq!((cards.len(), skipped), (1, 0), "reading {:?}", gift);
+        cards.remove(0)
+    }
+
+    #[test]
+    fn escaped_special_characters() {
+        let card = read_one(r"::1\:1::Is 1 \= 1 \{really\} \~ \#? {=Yes \#1 ~No\: \~2}");
+
+        assert_eq!(
+            card,
+            DeckCard::MultipleChoice {
+                question: String::from("Is 1 = 1 {really} ~ #?"),
+                choices: strings(&["Yes #1", "No: ~2"]),
+                answers: strings(&["Yes #1"]),
+            }
+        );
+    }
+
+    #[test]
+    fn special_characters_are_escaped_when_written() {
+        let card = DeckCard::MultipleChoice {
+            question: String::from("a~b=c#d{e}f:g\\h"),
+            choices: strings(&["=1", "~2"]),
+            answers: strings(&["=1"]),
+        };
+        let (gift, _) = write(&[card]);
+
+        assert_eq!(
+            gift,
+            "a\\~b\\=c\\#d\\{e\\}f\\:g\\\\h {\n\t=\\=1\n\t~\\~2\n}\n"
+        );
+    }
+
+    #[test]
+    fn numeric_answers() {
+        assert_eq!(
+            read_one("Pi? {#3.14:0.01}"),
+            blanks("Pi?", "_#3.14:0.01_", &["numeric"])
+        );
+        assert_eq!(
+            read_one("Between one and five? {#1..5}"),
+            blanks("Between one and five?", "_#1..5_", &["numeric"])
+        );
+
+        // Answers given partial credit are left out
+        assert_eq!(
+            read_one("Two? {#=2:0 =%50%3 =%100%4 : 1}"),
+            blanks("Two?", "_#2:0|#4:1_", &["numeric"])
+        );
+    }
+
+    #[test]
+    fn malformed_numeric_answers_are_skipped() {
+        assert_eq!(read("Pi? {#three}"), (Vec::new(), 1));
+    }
+
+    #[test]
+    fn short_answer_alternatives() {
+        assert_eq!(
+            read_one("Capital of France? {=Paris =paris =%50%Lyon =A|B}"),
+            blanks("Capital of France?", "_Paris|paris|A/B_", &[])
+        );
+        assert_eq!(
+            read_one("::Sky::The {=blue =azure} sky"),
+            blanks("Sky", "The _blue|azure_ sky", &[])
+        );
+    }
+
+    #[test]
+    fn short_answers_holding_underscores_are_skipped() {
+        assert_eq!(read("Name? {=snake_case}").1, 1);
+        assert_eq!(read("In snake_case {=underscores} separate words").1, 1);
+    }
+
+    #[test]
+    fn questions_without_a_card_type_are_skipped() {
+        let gift = "Describe Paris. {}\n\n\
+            Match the capitals. {=France -> Paris =Spain -> Madrid}\n\n\
+            // A comment\n\
+            $CATEGORY: Capitals\n\n\
+            Capital of France? {=Paris ~Lyon}";
+        let (cards, skipped) = read(gift);
+
+        assert_eq!(cards.len(), 1);
+        assert_eq!(skipped, 2);
+    }
+
+    #[test]
+    fn written_questions_are_read_back() {
+        let cards = vec![
+            DeckCard::MultipleChoice {
+                question: String::from("Capital of France: {city}?"),
+                choices: strings(&["Paris", "Lyon = no", "#3"]),
+                answers: strings(&["Paris"]),
+            },
+            DeckCard::MultipleChoice {
+                question: String::from("The sky is blue"),
+                choices: strings(&["True", "False"]),
+                answers: strings(&["True"]),
+            },
+            DeckCard::MultipleAnswer {
+                question: String::from("Primes?"),
+                choices: strings(&["2", "3", "4"]),
+                answers: strings(&["2", "3"]),
+            },
+            blanks("Pi", "_#3.14:0.01|#3_", &["numeric"]),
+            blanks("Range", "_#1..5_", &["numeric"]),
+            blanks("Sky", "_blue|azure_", &[]),
+        ];
+
+        let (gift, skipped) = write(&cards);
+        assert_eq!(skipped, 0);
+
+        let (read_cards, skipped) = read(&gift);
+        assert_eq!(skipped, 0);
+        assert_eq!(read_cards, cards);
+    }
+
+    #[test]
+    fn each_blank_is_written_as_a_question() {
+        let (gift, _) = write(&[
+            blanks("Capitals", "_Paris_ and _Rome|rome_", &[]),
+            DeckCard::FlashCard {
+                question: String::from("Q"),
+                answer: String::from("A"),
+                reversible: false,
+            },
+        ]);
+
+        assert_eq!(
+            gift,
+            "::Capitals::{=Paris} and Rome\n\n::Capitals::Paris and {=Rome =rome}\n"
+        );
+        assert_eq!(write(&[]).1, 0);
+    }
+}
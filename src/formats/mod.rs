@@ -0,0 +1,57 @@
+use regex::Regex;
+
+#[cfg(feature = "anki")]
+pub mod anki;
+pub mod gift;
+pub mod printable;
+pub(crate) mod spreadsheet;
+pub mod structured;
+
+/// Questions are written on a single line.
+pub(crate) fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Card content can't hold blank lines or card dividers.
+pub(crate) fn paragraph(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .replace("---", "—")
+}
+
+/// A blank's answer, as fill in the blanks content holds it. Answers are
+/// separated by `|` and blanks are marked with `_`, so `|` is written as `/`
+/// and answers holding `_` can't be held at all.
+pub(crate) fn blank_answer(answer: &str) -> Option<String> {
+    (!answer.contains('_')).then(|| answer.replace('|', "/"))
+}
+
+/// Convert HTML, such as an Anki field or Moodle question, into plain text.
+pub(crate) fn html_to_text(html: &str) -> String {
+    let breaks = Regex::new(r"(?i)<br\s*/?>|</div>|</p>|</li>").expect("Error with regex string.");
+    let tags = Regex::new(r"<[^>]*>|\[sound:[^\]]*\]").expect("Error with regex string.");
+
+    let text = breaks.replace_all(html, "\n");
+    let text = tags.replace_all(&text, "");
+
+    text.replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+        .lines()
+        .map(str::trim)
+        .collect::<Vec<&str>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+pub(crate) fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
@@ -0,0 +1,340 @@
+use rand::seq::SliceRandom;
+use regex::Regex;
+
+use super::escape_html;
+use super::structured::DeckCard;
+use crate::models::matching::numeric_range;
+
+/// Page styles, kept within the page so it can be printed on its own.
+const STYLE: &str = "
+    body { font-family: Georgia, serif; max-width: 46em; margin: 2em auto; line-height: 1.5; }
+    h1 { border-bottom: 2px solid #000; padding-bottom: 0.2em; }
+    li.card { margin-bottom: 1.5em; break-inside: avoid; }
+    .question { font-weight: bold; margin: 0 0 0.4em; }
+    .hint { font-style: italic; margin: 0 0 0.4em; }
+    ul.choices { list-style: none; padding-left: 0.5em; margin: 0; }
+    .blank { display: inline-block; min-width: 6em; border-bottom: 1px solid #000; }
+    .lines { height: 4.5em; background: repeating-linear-gradient(transparent 0 1.45em, #999 1.45em 1.5em); }
+    .answer-key { break-before: page; }
+";
+
+/// Shown in place of a blank within the Markdown question sheet.
+const MARKDOWN_BLANK: &str = r"\_\_\_\_\_\_\_\_";
+
+/// Render the deck as a standalone HTML page, with a question sheet followed
+/// by an answer key on a new page.
+pub fn to_html(title: &str, cards: &[DeckCard]) -> String {
+    let title = escape_html(title);
+    let questions: Vec<String> = cards.iter().map(html_question).collect();
+    let answers: Vec<String> = cards.iter().map(html_answer).collect();
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>{title}</title>
+<style>{STYLE}</style>
+</head>
+<body>
+<h1>{title}</h1>
+<ol>
+{}
+</ol>
+<section class=\"answer-key\">
+<h1>{title}: Answer Key</h1>
+<ol>
+{}
+</ol>
+</section>
+</body>
+</html>
+",
+        questions.join("\n"),
+        answers.join("\n")
+    )
+}
+
+fn html_question(card: &DeckCard) -> String {
+    let (question, body) = match card {
+        DeckCard::FlashCard { question, .. } | DeckCard::Plugin { question, .. } => {
+            (question, String::from("<div class=\"lines\"></div>"))
+        }
+        DeckCard::MultipleChoice {
+            question, choices, ..
+        } => (question, html_choices("○", choices)),
+        DeckCard::MultipleAnswer {
+            question, choices, ..
+        } => (
+            question,
+            format!(
+                "<p class=\"hint\">Select every correct answer</p>\n{}",
+                html_choices("☐", choices)
+            ),
+        ),
+        DeckCard::FillInTheBlanks {
+            question, content, ..
+        } => (
+            question,
+            format!(
+                "<p>{}</p>",
+                fill_blanks(content, &escape_html, |_| String::from(
+                    "<span class=\"blank\"></span>"
+                ))
+                .replace('\n', "<br>\n")
+            ),
+        ),
+        DeckCard::Order { question, answer } => (
+            question,
+            format!(
+                "<p class=\"hint\">Number the items in order</p>\n{}",
+                html_choices("☐", &shuffled(answer))
+            ),
+        ),
+    };
+
+    format!(
+        "<li class=\"card\">\n<p class=\"question\">{}</p>\n{}\n</li>",
+        escape_html(question),
+        body
+    )
+}
+
+fn html_answer(card: &DeckCard) -> String {
+    let (question, body) = match card {
+        DeckCard::FlashCard {
+            question, answer, ..
+        }
+        | DeckCard::Plugin {
+            question,
+            content: answer,
+            ..
+        } => (
+            question,
+            format!("<p>{}</p>", escape_html(answer).replace('\n', "<br>\n")),
+        ),
+        DeckCard::MultipleChoice {
+            question, answers, ..
+        }
+        | DeckCard::MultipleAnswer {
+            question, answers, ..
+        } => (question, html_list("ul", answers)),
+        DeckCard::FillInTheBlanks {
+            question, content, ..
+        } => (
+            question,
+            format!(
+                "<p>{}</p>",
+                fill_blanks(content, &escape_html, |answers| format!(
+                    "<strong>{}</strong>",
+                    escape_html(&describe_answers(answers))
+                ))
+                .replace('\n', "<br>\n")
+            ),
+        ),
+        DeckCard::Order { question, answer } => (question, html_list("ol", answer)),
+    };
+
+    format!(
+        "<li class=\"card\">\n<p class=\"question\">{}</p>\n{}\n</li>",
+        escape_html(question),
+        body
+    )
+}
+
+fn html_choices(marker: &str, choices: &[String]) -> String {
+    let choices: Vec<String> = choices
+        .iter()
+        .map(|choice| format!("<li>{} {}</li>", marker, escape_html(choice)))
+        .collect();
+
+    format!("<ul class=\"choices\">\n{}\n</ul>", choices.join("\n"))
+}
+
+fn html_list(tag: &str, items: &[String]) -> String {
+    let items: Vec<String> = items
+        .iter()
+        .map(|item| format!("<li>{}</li>", escape_html(item)))
+        .collect();
+
+    format!("<{tag}>\n{}\n</{tag}>", items.join("\n"))
+}
+
+/// Render the deck as Markdown ready to be converted into a PDF, with a
+/// question sheet followed by an answer key on a new page.
+pub fn to_markdown(title: &str, cards: &[DeckCard]) -> String {
+    let questions: Vec<String> = cards
+        .iter()
+        .enumerate()
+        .map(|(i, card)| markdown_question(i + 1, card))
+        .collect();
+    let answers: Vec<String> = cards
+        .iter()
+        .enumerate()
+        .map(|(i, card)| markdown_answer(i + 1, card))
+        .collect();
+
+    format!(
+        "# {title}\n\n{}\n\n<div style=\"page-break-before: always;\"></div>\n\n# {title}: Answer Key\n\n{}\n",
+        questions.join("\n\n"),
+        answers.join("\n\n")
+    )
+}
+
+fn markdown_question(number: usize, card: &DeckCard) -> String {
+    let (question, body) = match card {
+        DeckCard::FlashCard { question, .. } | DeckCard::Plugin { question, .. } => {
+            (question, format!("Answer: {}", MARKDOWN_BLANK.repeat(4)))
+        }
+        DeckCard::MultipleChoice {
+            question, choices, ..
+        } => (question, markdown_choices("( ) ", choices)),
+        DeckCard::MultipleAnswer {
+            question, choices, ..
+        } => (
+            question,
+            format!(
+                "*Select every correct answer*\n\n{}",
+                markdown_choices("[ ] ", choices)
+            ),
+        ),
+        DeckCard::FillInTheBlanks {
+            question, content, ..
+        } => (
+            question,
+            fill_blanks(content, &str::to_string, |_| String::from(MARKDOWN_BLANK)),
+        ),
+        DeckCard::Order { question, answer } => (
+            question,
+            format!(
+                "*Number the items in order*\n\n{}",
+                markdown_choices("[ ] ", &shuffled(answer))
+            ),
+        ),
+    };
+
+    markdown_item(number, question, &body)
+}
+
+fn markdown_answer(number: usize, card: &DeckCard) -> String {
+    let (question, body) = match card {
+        DeckCard::FlashCard {
+            question, answer, ..
+        }
+        | DeckCard::Plugin {
+            question,
+            content: answer,
+            ..
+        } => (question, answer.clone()),
+        DeckCard::MultipleChoice {
+            question, answers, ..
+        }
+        | DeckCard::MultipleAnswer {
+            question, answers, ..
+        } => (question, markdown_choices("", answers)),
+        DeckCard::FillInTheBlanks {
+            question, content, ..
+        } => (
+            question,
+            fill_blanks(content, &str::to_string, |answers| {
+                format!("**{}**", describe_answers(answers))
+            }),
+        ),
+        DeckCard::Order { question, answer } => {
+            let items: Vec<String> = answer
+                .iter()
+                .enumerate()
+                .map(|(i, item)| format!("{}. {}", i + 1, item))
+                .collect();
+
+            (question, items.join("\n"))
+        }
+    };
+
+    markdown_item(number, question, &body)
+}
+
+fn markdown_choices(marker: &str, choices: &[String]) -> String {
+    let choices: Vec<String> = choices
+        .iter()
+        .map(|choice| format!("- {}{}", marker, choice))
+        .collect();
+
+    choices.join("\n")
+}
+
+/// A numbered question with its body indented beneath it. Lines of text end
+/// with two spaces so they aren't joined together.
+fn markdown_item(number: usize, question: &str, body: &str) -> String {
+    let list_item = Regex::new(r"^(-|\d+\.) ").expect("Error with regex string.");
+    let body: Vec<String> = body
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else if list_item.is_match(line) {
+                format!("   {}", line)
+            } else {
+                format!("   {}  ", line)
+            }
+        })
+        .collect();
+
+    format!("{}. **{}**\n\n{}", number, question, body.join("\n"))
+}
+
+/// Replace each blank within the content, e.g. `_AUS|Australia_`, with the
+/// blank's answers rendered by `blank`, and the text between by `text`.
+fn fill_blanks(
+    content: &str,
+    text: &dyn Fn(&str) -> String,
+    blank: impl Fn(&[&str]) -> String,
+) -> String {
+    let re = Regex::new(r"_(.*?)_").expect("Error with regex string.");
+    let mut filled = String::new();
+    let mut last = 0;
+
+    for captures in re.captures_iter(content) {
+        let (Some(whole), Some(answers)) = (captures.get(0), captures.get(1)) else {
+            continue;
+        };
+
+        filled.push_str(&text(&content[last..whole.start()]));
+        filled.push_str(&blank(&answers.as_str().split('|').collect::<Vec<&str>>()));
+        last = whole.end();
+    }
+
+    filled.push_str(&text(&content[last..]));
+    filled
+}
+
+/// The answers to a blank as they'd be read, e.g. `3.14 ± 0.01` rather than
+/// `#3.14:0.01`.
+fn describe_answers(answers: &[&str]) -> String {
+    answers
+        .iter()
+        .map(|answer| {
+            let number = answer
+                .strip_prefix('#')
+                .filter(|_| numeric_range(answer).is_some());
+
+            match number {
+                Some(number) => match (number.split_once(".."), number.split_once(':')) {
+                    (Some((min, max)), _) => format!("{} to {}", min.trim(), max.trim()),
+                    (_, Some((value, tolerance))) => {
+                        format!("{} ± {}", value.trim(), tolerance.trim())
+                    }
+                    _ => number.trim().to_string(),
+                },
+                None => answer.to_string(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" / ")
+}
+
+fn shuffled(items: &[String]) -> Vec<String> {
+    let mut items = items.to_vec();
+    items.shuffle(&mut rand::thread_rng());
+    items
+}
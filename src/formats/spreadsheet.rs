@@ -0,0 +1,112 @@
+use super::structured::DeckCard;
+use crate::models::{
+    card::Card,
+    card_types::{
+        fill_in_the_blanks::FillInTheBlanks, flashcard::FlashCard, multiple_answer::MultipleAnswer,
+        multiple_choice::MultipleChoice, order::Order,
+    },
+    errors::parsing_error::ParsingError,
+    matching::MatchOptions,
+};
+
+/// Parse a deck written as CSV or TSV, with one card per row.
+///
+/// Columns are the card type, question, answer and optionally tags, e.g.
+/// `multiple_choice,Capital of France?,*Paris|London|Berlin,geography`.
+pub fn parse(content: &str, delimiter: u8) -> Result<Vec<Card>, ParsingError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(content.as_bytes());
+
+    let mut cards = Vec::new();
+    for (index, record) in reader.records().enumerate() {
+        let record = record.map_err(|err| ParsingError::InvalidRow(err.to_string()))?;
+
+        let card_type = record.get(0).unwrap_or_default();
+        let question = record.get(1).unwrap_or_default();
+        let answer = record.get(2).unwrap_or_default();
+
+        // The header row is optional
+        if index == 0 && card_type.eq_ignore_ascii_case("type") {
+            continue;
+        }
+
+        // Blank rows are skipped
+        if record.iter().all(str::is_empty) {
+            continue;
+        }
+
+        // The card type can be followed by options e.g. `fill_in_the_blanks ignore_case`
+        let mut type_line = card_type.split_whitespace();
+        let card_type = type_line.next().unwrap_or_default().to_lowercase();
+
+        let mut card = deck_card(&card_type, question, answer).into_card(MatchOptions::default())?;
+        card.apply_options(&type_line.collect::<Vec<&str>>())?;
+        cards.push(card);
+    }
+
+    if cards.is_empty() {
+        return Err(ParsingError::NoCards);
+    }
+
+    Ok(cards)
+}
+
+/// Read the answer column in the format the card type expects.
+fn deck_card(card_type: &str, question: &str, answer: &str) -> DeckCard {
+    let question = question.to_string();
+
+    // Choices and items are separated by a pipe, with correct choices marked by `*`
+    let items = || {
+        answer
+            .split('|')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+    };
+    let choices = || {
+        items()
+            .map(|item| item.strip_prefix('*').map_or(item, str::trim).to_string())
+            .collect()
+    };
+    let answers = || {
+        items()
+            .filter_map(|item| item.strip_prefix('*'))
+            .map(|item| item.trim().to_string())
+            .collect()
+    };
+
+    match card_type {
+        FlashCard::NAME => DeckCard::FlashCard {
+            question,
+            answer: answer.to_string(),
+            reversible: false,
+        },
+        MultipleChoice::NAME => DeckCard::MultipleChoice {
+            question,
+            choices: choices(),
+            answers: answers(),
+        },
+        MultipleAnswer::NAME => DeckCard::MultipleAnswer {
+            question,
+            choices: choices(),
+            answers: answers(),
+        },
+        FillInTheBlanks::NAME => DeckCard::FillInTheBlanks {
+            question,
+            content: answer.to_string(),
+            options: Vec::new(),
+        },
+        Order::NAME => DeckCard::Order {
+            question,
+            answer: items().map(str::to_string).collect(),
+        },
+        _ => DeckCard::Plugin {
+            card_type: card_type.to_string(),
+            question,
+            content: answer.to_string(),
+        },
+    }
+}
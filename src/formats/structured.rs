@@ -0,0 +1,279 @@
+use serde::{Deserialize, Serialize};
+
+use super::{paragraph, single_line};
+use crate::models::{
+    card::Card,
+    card_types::{
+        fill_in_the_blanks::FillInTheBlanks, flashcard::FlashCard, multiple_answer::MultipleAnswer,
+        multiple_choice::MultipleChoice, order::Order,
+    },
+    errors::parsing_error::ParsingError,
+    matching::MatchOptions,
+};
+#[cfg(feature = "plugins")]
+use crate::models::{card_types::plugin::PluginCard, plugin};
+
+/// The JSON Schema describing decks written as JSON or TOML.
+pub const SCHEMA: &str = include_str!("../../schema/deck.schema.json");
+
+/// A deck written as JSON or TOML, generally by another tool.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Deck {
+    /// Options applied to every card, e.g. `ignore_case`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+    pub cards: Vec<DeckCard>,
+}
+
+/// A card, with a field for each part of the card type.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum DeckCard {
+    #[serde(rename = "flashcard")]
+    FlashCard {
+        question: String,
+        answer: String,
+        #[serde(default, skip_serializing_if = "is_false")]
+        reversible: bool,
+    },
+    MultipleChoice {
+        question: String,
+        choices: Vec<String>,
+        /// The correct choice
+        answers: Vec<String>,
+    },
+    MultipleAnswer {
+        question: String,
+        choices: Vec<String>,
+        /// Every correct choice
+        answers: Vec<String>,
+    },
+    FillInTheBlanks {
+        question: String,
+        /// The text with its blanks surrounded by underscores, e.g. `_blue_`
+        content: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        options: Vec<String>,
+    },
+    Order {
+        question: String,
+        /// Items in the correct order
+        answer: Vec<String>,
+    },
+    /// A card type defined by a plugin
+    Plugin {
+        /// The name of the plugin's card type
+        card_type: String,
+        question: String,
+        /// Everything following the question, as read by the plugin
+        content: String,
+    },
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+impl Deck {
+    /// Create each of the deck's cards, with the deck's options applied.
+    pub fn into_cards(self) -> Result<Vec<Card>, ParsingError> {
+        if self.cards.is_empty() {
+            return Err(ParsingError::NoCards);
+        }
+
+        let mut deck_matching = MatchOptions::default();
+        for option in &self.options {
+            deck_matching.set(option)?;
+        }
+
+        self.cards
+            .into_iter()
+            .map(|card| card.into_card(deck_matching))
+            .collect()
+    }
+}
+
+impl DeckCard {
+    /// Create the card from its fields, checking each of them the same way as
+    /// when the card is written in Markdown.
+    pub fn into_card(self, deck_matching: MatchOptions) -> Result<Card, ParsingError> {
+        let (mut card, options) = match self {
+            DeckCard::FlashCard {
+                question,
+                answer,
+                reversible,
+            } => {
+                let mut card = FlashCard::new(question, answer)?;
+                card.reversible = reversible;
+                (Card::FlashCard(card), Vec::new())
+            }
+            DeckCard::MultipleChoice {
+                question,
+                choices,
+                answers,
+            } => (
+                Card::MultipleChoice(MultipleChoice::new(question, choices, answers)?),
+                Vec::new(),
+            ),
+            DeckCard::MultipleAnswer {
+                question,
+                choices,
+                answers,
+            } => (
+                Card::MultipleAnswer(MultipleAnswer::new(question, choices, answers)?),
+                Vec::new(),
+            ),
+            DeckCard::FillInTheBlanks {
+                question,
+                content,
+                options,
+            } => (
+                Card::FillInTheBlanks(FillInTheBlanks::new(question, content)?),
+                options,
+            ),
+            DeckCard::Order { question, answer } => {
+                (Card::Order(Order::new(question, answer)?), Vec::new())
+            }
+            #[cfg(feature = "plugins")]
+            DeckCard::Plugin {
+                card_type,
+                question,
+                content,
+            } => {
+                let plugin =
+                    plugin::find(&card_type.to_lowercase()).ok_or(ParsingError::NoCardType)?;
+                (
+                    Card::Plugin(PluginCard::new(plugin, question, content)?),
+                    Vec::new(),
+                )
+            }
+            #[cfg(not(feature = "plugins"))]
+            DeckCard::Plugin { .. } => return Err(ParsingError::NoCardType),
+        };
+
+        card.as_card_type_mut().set_matching(deck_matching);
+        card.apply_options(&options.iter().map(String::as_str).collect::<Vec<&str>>())?;
+
+        Ok(card)
+    }
+}
+
+/// Parse a deck written as JSON.
+pub(crate) fn parse_json(content: &str) -> Result<Vec<Card>, ParsingError> {
+    let deck: Deck =
+        serde_json::from_str(content).map_err(|err| ParsingError::InvalidDeck(err.to_string()))?;
+
+    deck.into_cards()
+}
+
+/// Parse a deck written as TOML.
+pub(crate) fn parse_toml(content: &str) -> Result<Vec<Card>, ParsingError> {
+    let deck: Deck =
+        toml::from_str(content).map_err(|err| ParsingError::InvalidDeck(err.to_string()))?;
+
+    deck.into_cards()
+}
+
+/// Write the deck in the Markdown format, e.g. when importing it. Blank lines
+/// and dividers within a card's content can't be written in Markdown, so
+/// they're removed.
+pub fn to_markdown(deck: &Deck) -> Result<String, ParsingError> {
+    let mut sections = Vec::new();
+
+    if !deck.options.is_empty() {
+        sections.push(format!("options {}", deck.options.join(" ")));
+    }
+
+    for card in &deck.cards {
+        let (card_type, question, body) = match card {
+            DeckCard::FlashCard {
+                question,
+                answer,
+                reversible,
+            } => (
+                format!("flashcard{}", if *reversible { " reversible" } else { "" }),
+                question,
+                paragraph(answer),
+            ),
+            DeckCard::MultipleChoice {
+                question,
+                choices,
+                answers,
+            } => (
+                String::from("multiple_choice"),
+                question,
+                choice_lines(choices, answers, ("* ", "- "))?,
+            ),
+            DeckCard::MultipleAnswer {
+                question,
+                choices,
+                answers,
+            } => (
+                String::from("multiple_answer"),
+                question,
+                choice_lines(choices, answers, ("[*] ", "[ ] "))?,
+            ),
+            DeckCard::FillInTheBlanks {
+                question,
+                content,
+                options,
+            } => (
+                format!("fill_in_the_blanks {}", options.join(" "))
+                    .trim_end()
+                    .to_string(),
+                question,
+                paragraph(content),
+            ),
+            DeckCard::Order { question, answer } => (
+                String::from("order"),
+                question,
+                answer
+                    .iter()
+                    .enumerate()
+                    .map(|(i, item)| format!("{}. {}", i + 1, single_line(item)))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            ),
+            DeckCard::Plugin {
+                card_type,
+                question,
+                content,
+            } => (single_line(card_type), question, paragraph(content)),
+        };
+
+        sections.push(format!(
+            "{}\n\n# {}\n{}",
+            card_type,
+            single_line(question),
+            body
+        ));
+    }
+
+    Ok(sections.join("\n\n---\n\n"))
+}
+
+/// Write each choice prefixed by whether it's correct, checking every answer is
+/// one of the choices.
+fn choice_lines(
+    choices: &[String],
+    answers: &[String],
+    (correct, incorrect): (&str, &str),
+) -> Result<String, ParsingError> {
+    if let Some(answer) = answers.iter().find(|answer| !choices.contains(answer)) {
+        return Err(ParsingError::UnknownAnswer(answer.clone()));
+    }
+
+    Ok(choices
+        .iter()
+        .map(|choice| {
+            let prefix = if answers.contains(choice) {
+                correct
+            } else {
+                incorrect
+            };
+            format!("{}{}", prefix, single_line(choice))
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
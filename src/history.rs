@@ -0,0 +1,121 @@
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use oxycards::{Card, UserAnswer};
+
+use crate::paths;
+
+/// The outcome of a single card within a study session.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CardRecord {
+    pub question: String,
+    pub outcome: UserAnswer,
+}
+
+/// A finished study session for a deck.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SessionRecord {
+    /// Absolute path of the deck that was studied
+    pub deck: String,
+    pub date: DateTime<Local>,
+    /// Time spent within the session, in seconds
+    pub duration: u64,
+    pub cards: Vec<CardRecord>,
+}
+
+impl SessionRecord {
+    pub fn new(deck: &Path, date: DateTime<Local>, duration: u64, cards: &[Card]) -> Self {
+        Self {
+            deck: History::deck_key(deck),
+            date,
+            duration,
+            cards: cards
+                .iter()
+                .map(|card| CardRecord {
+                    question: card.as_card_type().question().to_string(),
+                    outcome: card.as_card_type().user_answer(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Number of cards answered correctly.
+    pub fn correct(&self) -> usize {
+        self.count(UserAnswer::Correct)
+    }
+
+    /// Number of cards answered incorrectly.
+    pub fn incorrect(&self) -> usize {
+        self.count(UserAnswer::Incorrect)
+    }
+
+    /// Check if any card was answered during the session.
+    pub fn is_empty(&self) -> bool {
+        self.correct() + self.incorrect() == 0
+    }
+
+    fn count(&self, outcome: UserAnswer) -> usize {
+        self.cards
+            .iter()
+            .filter(|card| card.outcome == outcome)
+            .count()
+    }
+}
+
+/// Local store of every finished session, kept as one JSON record per line.
+pub struct History;
+
+impl History {
+    /// Location of the history store within the users data directory.
+    pub fn path() -> Result<PathBuf, io::Error> {
+        Ok(paths::data_dir()?.join("history.jsonl"))
+    }
+
+    /// Key used to identify a deck across sessions.
+    pub fn deck_key(deck: &Path) -> String {
+        fs::canonicalize(deck)
+            .unwrap_or_else(|_| deck.to_path_buf())
+            .to_string_lossy()
+            .to_string()
+    }
+
+    /// Append a session to the end of the history store.
+    pub fn append(record: &SessionRecord) -> Result<(), io::Error> {
+        let path = History::path()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)
+    }
+
+    /// Load every session held within the history store, along with the
+    /// number of lines skipped because they couldn't be read, e.g. a record
+    /// cut short by a crash.
+    pub fn load() -> Result<(Vec<SessionRecord>, usize), io::Error> {
+        let path = History::path()?;
+
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok((vec![], 0)),
+            Err(err) => return Err(err),
+        };
+
+        let mut sessions = Vec::new();
+        let mut skipped = 0;
+        for line in content.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(session) => sessions.push(session),
+                Err(_) => skipped += 1,
+            }
+        }
+
+        Ok((sessions, skipped))
+    }
+}
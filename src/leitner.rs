@@ -0,0 +1,114 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use oxycards::{Card, UserAnswer};
+
+use crate::{history::History, paths};
+
+/// Number of boxes a card can move through.
+pub const BOXES: u8 = 5;
+
+/// The box a card sits within.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BoxedCard {
+    pub box_number: u8,
+    pub last_reviewed: NaiveDate,
+}
+
+impl BoxedCard {
+    /// The day the card should next be reviewed.
+    pub fn due(&self) -> NaiveDate {
+        self.last_reviewed + Duration::days(Leitner::interval(self.box_number))
+    }
+}
+
+/// Local store of the box each card sits within, keyed by deck then by the
+/// card's question. Cards not yet studied start in the first box.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct Leitner {
+    pub decks: BTreeMap<String, BTreeMap<String, BoxedCard>>,
+}
+
+impl Leitner {
+    /// Location of the Leitner store within the users data directory.
+    pub fn path() -> Result<PathBuf, io::Error> {
+        Ok(paths::data_dir()?.join("leitner.json"))
+    }
+
+    pub fn load() -> Result<Self, io::Error> {
+        match fs::read_to_string(Leitner::path()?) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Leitner::default()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), io::Error> {
+        let path = Leitner::path()?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// Days between reviews of the cards within a box, doubling with each box.
+    pub fn interval(box_number: u8) -> i64 {
+        1 << (box_number.clamp(1, BOXES) - 1)
+    }
+
+    /// Check if a card should be reviewed on the given day.
+    pub fn is_due(&self, deck: &Path, card: &Card, today: NaiveDate) -> bool {
+        self.decks
+            .get(&History::deck_key(deck))
+            .and_then(|cards| cards.get(card.as_card_type().question()))
+            .is_none_or(|boxed| boxed.due() <= today)
+    }
+
+    /// The earliest day any of a deck's cards should be reviewed.
+    pub fn next_due(&self, deck: &Path, cards: &[Card]) -> Option<NaiveDate> {
+        let boxed = self.decks.get(&History::deck_key(deck))?;
+
+        cards
+            .iter()
+            .map(Card::as_card_type)
+            .filter_map(|card| boxed.get(card.question()).map(BoxedCard::due))
+            .min()
+    }
+
+    /// Move each answered card up a box when correct, or back to the first box
+    /// when incorrect. Only the first answer to a card counts when it was
+    /// studied more than once.
+    pub fn record(&mut self, deck: &Path, cards: &[Card], today: NaiveDate) {
+        let boxed = self.decks.entry(History::deck_key(deck)).or_default();
+        let mut recorded = HashSet::new();
+
+        for card in cards.iter().map(Card::as_card_type) {
+            if card.check_answered() && !recorded.insert(card.question()) {
+                continue;
+            }
+
+            let box_number = boxed.get(card.question()).map_or(1, |b| b.box_number);
+
+            let box_number = match card.user_answer() {
+                UserAnswer::Correct => (box_number + 1).min(BOXES),
+                UserAnswer::Incorrect => 1,
+                UserAnswer::Undecided => continue,
+            };
+
+            boxed.insert(
+                card.question().to_string(),
+                BoxedCard {
+                    box_number,
+                    last_reviewed: today,
+                },
+            );
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Parse, answer and write oxycards decks.
+//!
+//! Decks written in any [`FileType`] are parsed into [`Card`]s, which are
+//! answered and validated the same way as within the terminal application.
+//!
+//! ```
+//! use oxycards::{parse_deck, Card, FileType, UserAnswer};
+//!
+//! let deck = "multiple_choice\n\n# What is the capital of France?\n* Paris\n- London";
+//! let mut cards = parse_deck(deck, FileType::Markdown)?;
+//!
+//! if let Card::MultipleChoice(card) = &mut cards[0] {
+//!     card.choices.items[0].select();
+//! }
+//! assert_eq!(cards[0].as_card_type_mut().validate_answer(), UserAnswer::Correct);
+//!
+//! // Cards can be written back into a Markdown deck
+//! let written = Card::write_deck(&cards, &Default::default())?;
+//! assert_eq!(parse_deck(&written, FileType::Markdown)?.len(), 1);
+//! # Ok::<(), oxycards::ParsingError>(())
+//! ```
+
+pub mod formats;
+mod models;
+
+pub use models::card::Card;
+pub use models::card_state::CardState;
+pub use models::card_types::{
+    fill_in_the_blanks::FillInTheBlanks,
+    flashcard::{FlashCard, Reverse},
+    multiple_answer::MultipleAnswer,
+    multiple_choice::MultipleChoice,
+    order::Order,
+    CardType,
+};
+pub use models::choice::Choice;
+pub use models::errors::{
+    import_error::ImportError, parsing_error::ParsingError, plugin_error::PluginError,
+};
+pub use models::file_type::FileType;
+pub use models::matching::MatchOptions;
+pub use models::stateful_list::StatefulList;
+pub use models::user_answer::UserAnswer;
+
+// Drawing cards and reading key presses within a terminal
+#[cfg(feature = "tui")]
+pub use models::{
+    card_types::CardView,
+    keybindings::{Action, Key, KeyBindings, KeyContext},
+    theme::{Highlight, Theme},
+};
+
+// Card types defined by Rhai scripts
+#[cfg(feature = "plugins")]
+pub use models::{card_types::plugin::PluginCard, plugin};
+
+/// Parse a deck written in any of the supported formats into its cards.
+pub fn parse_deck(content: &str, file_type: FileType) -> Result<Vec<Card>, ParsingError> {
+    match file_type {
+        FileType::Markdown => Card::card_parser(content.to_string()),
+        FileType::Csv => formats::spreadsheet::parse(content, b','),
+        FileType::Tsv => formats::spreadsheet::parse(content, b'\t'),
+        FileType::Json => formats::structured::parse_json(content),
+        FileType::Toml => formats::structured::parse_toml(content),
+    }
+}
@@ -1,24 +1,45 @@
-pub mod models;
+pub mod args;
+pub mod commands;
+pub mod config;
+pub mod cram;
+pub mod errors;
+pub mod history;
+pub mod leitner;
+pub mod paths;
+pub mod score;
+pub mod search;
+pub mod session;
 pub mod ui;
 
+use args::{Args, Command};
+use chrono::{DateTime, Local};
 use clap::Parser;
-use models::args::Args;
-use models::errors::errors::Errors;
+use errors::Errors;
+use history::{History, SessionRecord};
+use session::SavedSession;
 
+use std::io::Write;
 use std::path::Path;
+use std::time::Instant;
 use std::{error::Error, fs, io};
 
-use crossterm::event::{self, Event, KeyCode};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
 use crossterm::execute;
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 
-use models::card::Card;
-use models::stateful_list::StatefulList;
-use models::user_answer::UserAnswer;
+use config::Config;
+use cram::Cram;
+use leitner::Leitner;
+use oxycards::{
+    plugin, Action, Card, FileType, KeyContext, ParsingError, Reverse, StatefulList, UserAnswer,
+};
+use score::Score;
+use search::Search;
 
 use tui::backend::{Backend, CrosstermBackend};
+use tui::widgets::ListState;
 use tui::Terminal;
 use ui::ui;
 
@@ -27,42 +48,219 @@ pub enum InputMode {
     Editing,
 }
 
-pub struct Score {
-    incorrect: usize,
-    correct: usize,
-}
-
-impl Score {
-    fn add_incorrect(&mut self) {
-        self.incorrect += 1;
-    }
-
-    fn add_correct(&mut self) {
-        self.correct += 1;
-    }
-}
-
-impl Default for Score {
-    fn default() -> Self {
-        Self {
-            incorrect: 0,
-            correct: 0,
-        }
-    }
-}
-
 pub struct AppState {
     pub cards: StatefulList<Card>,
     pub input_mode: InputMode,
     pub score: Score,
+    pub started_at: DateTime<Local>,
+    pub timer: Instant,
+    /// Time spent within previous runs of a resumed session, in seconds
+    pub elapsed: u64,
+    pub show_quit_popup: bool,
+    /// Answers are drafted rather than graded until the deck is submitted
+    pub review: bool,
+    pub show_submit_popup: bool,
+    /// Incorrectly answered cards are queued up again when cramming
+    pub cram: Option<Cram>,
+    /// Progress is saved on quitting so the session can be resumed later
+    pub resumable: bool,
+    pub show_help: bool,
+    /// Number of lines the help overlay has been scrolled down by
+    pub help_scroll: u16,
+    pub show_overview: bool,
+    /// The card highlighted within the card overview
+    pub overview: ListState,
+    pub search: Search,
+    pub config: Config,
 }
 
 impl AppState {
-    fn new(cards: Vec<Card>) -> Self {
+    fn new(cards: Vec<Card>, config: Config) -> Self {
         Self {
             cards: StatefulList::with_items(cards),
             score: Score::default(),
             input_mode: InputMode::Normal,
+            started_at: Local::now(),
+            timer: Instant::now(),
+            elapsed: 0,
+            show_quit_popup: false,
+            review: false,
+            show_submit_popup: false,
+            cram: None,
+            resumable: true,
+            show_help: false,
+            help_scroll: 0,
+            show_overview: false,
+            overview: ListState::default(),
+            search: Search::default(),
+            config,
+        }
+    }
+
+    /// Where key presses are currently being read from.
+    pub fn key_context(&self) -> KeyContext {
+        if self.show_quit_popup || self.show_submit_popup {
+            return KeyContext::Popup;
+        }
+
+        if self.show_help {
+            return KeyContext::Help;
+        }
+
+        if self.show_overview {
+            return KeyContext::Overview;
+        }
+
+        if self.search.input.is_some() {
+            return KeyContext::Search;
+        }
+
+        if let Some(card) = self.cards.selected().map(|i| &self.cards.items[i]) {
+            if card.as_card_type().awaiting_grade() {
+                return KeyContext::Validation;
+            }
+        }
+
+        match self.input_mode {
+            InputMode::Normal => KeyContext::Normal,
+            InputMode::Editing => KeyContext::Editing,
+        }
+    }
+
+    /// Move to the next card.
+    fn next_card(&mut self) {
+        self.cards.next();
+        self.update_input_mode();
+    }
+
+    /// Move to the previous card.
+    fn previous_card(&mut self) {
+        self.cards.previous();
+        self.update_input_mode();
+    }
+
+    /// Jump straight to a card.
+    fn select_card(&mut self, index: usize) {
+        self.cards.select(index);
+        self.update_input_mode();
+    }
+
+    /// Check if validating the selected card submits the whole deck instead.
+    pub fn submits_deck(&self) -> bool {
+        let selected = self.cards.selected().map(|i| &self.cards.items[i]);
+
+        self.review && !selected.is_some_and(|card| card.as_card_type().self_graded())
+    }
+
+    /// Grade every drafted answer, ending the review.
+    fn submit(&mut self) {
+        for index in 0..self.cards.items.len() {
+            let card = self.cards.items[index].as_card_type_mut();
+
+            // Cards graded by the user are graded as they go
+            if card.check_answered() || card.self_graded() {
+                continue;
+            }
+
+            card.validate_answer();
+            self.record_answer(index);
+        }
+
+        self.review = false;
+        self.update_input_mode();
+    }
+
+    /// Score the answer given to the selected card.
+    fn record_selected_answer(&mut self) {
+        if let Some(index) = self.cards.selected() {
+            self.record_answer(index);
+        }
+    }
+
+    /// Score the answer given to a card, queueing the card up again if it was
+    /// answered incorrectly while cramming.
+    fn record_answer(&mut self, index: usize) {
+        let answer = self.cards.items[index].as_card_type().user_answer();
+
+        match answer {
+            UserAnswer::Correct => self.score.add_correct(),
+            UserAnswer::Incorrect => self.score.add_incorrect(),
+            UserAnswer::Undecided => return,
+        }
+
+        if let Some(cram) = self.cram.as_mut() {
+            if let Some(position) = cram.record(index, answer, self.cards.items.len()) {
+                let card = self.cards.items[index].fresh();
+                self.cards.items.insert(position, card);
+                self.search.insert_copy(index, position);
+            }
+        }
+    }
+
+    /// Start editing when landing on a card answered by typing.
+    fn update_input_mode(&mut self) {
+        self.input_mode = match self.cards.selected_value() {
+            Some(card) if card.as_card_type().takes_text() => InputMode::Editing,
+            _ => InputMode::Normal,
+        };
+    }
+
+    /// Check if quitting now would leave progress behind that the user may not
+    /// expect to lose.
+    fn has_progress(&self) -> bool {
+        !self.is_finished()
+            && self
+                .cards
+                .items
+                .iter()
+                .any(|card| card.as_card_type().has_progress())
+    }
+
+    /// Continue a previously saved session with its progress already restored
+    /// onto `cards`.
+    fn resume(cards: Vec<Card>, config: Config, saved: SavedSession) -> Self {
+        let mut app_state = AppState::new(cards, config);
+
+        app_state.cards.select(saved.current_card);
+        app_state.score = saved.score;
+        app_state.started_at = saved.started_at;
+        app_state.elapsed = saved.elapsed;
+
+        app_state
+    }
+
+    /// Total time spent within the session, in seconds.
+    fn elapsed(&self) -> u64 {
+        self.elapsed + self.timer.elapsed().as_secs()
+    }
+
+    /// Check if every card has been answered.
+    fn is_finished(&self) -> bool {
+        self.cards
+            .items
+            .iter()
+            .all(|card| card.as_card_type().check_answered())
+    }
+
+    /// Create a record of the session for the history store.
+    fn session_record(&self, deck: &Path) -> SessionRecord {
+        SessionRecord::new(deck, self.started_at, self.elapsed(), &self.cards.items)
+    }
+
+    /// Capture the sessions progress so it can be resumed later.
+    fn saved_session(&self, deck_hash: String) -> SavedSession {
+        SavedSession {
+            deck_hash,
+            started_at: self.started_at,
+            elapsed: self.elapsed(),
+            current_card: self.cards.selected().unwrap_or(0),
+            score: self.score.clone(),
+            cards: self
+                .cards
+                .items
+                .iter()
+                .map(|card| card.as_card_type().state())
+                .collect(),
         }
     }
 }
@@ -71,24 +269,164 @@ fn read_from_file(path: &Path) -> Result<String, io::Error> {
     fs::read_to_string(path)
 }
 
+fn parse_cards(content: &str, file_type: FileType) -> Vec<Card> {
+    match oxycards::parse_deck(content, file_type) {
+        Ok(cards) => cards,
+        Err(err) => Errors::throw_parsing_error(err),
+    }
+}
+
+/// Ask the user if they would like to resume a previously saved session.
+fn prompt_resume(path: &Path, cards: &[Card]) -> Result<bool, io::Error> {
+    let answered = cards
+        .iter()
+        .filter(|card| card.as_card_type().check_answered())
+        .count();
+
+    print!(
+        "Resume your previous session of {} ({}/{} cards answered)? [Y/n] ",
+        path.display(),
+        answered,
+        cards.len()
+    );
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(!input.trim().to_lowercase().starts_with('n'))
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
     let args = Args::parse();
-    let path = Path::new(&args.path);
+
+    // Card types from plugins can be used by every command. A broken plugin
+    // only stops decks using its card type from being parsed
+    if let Ok(dir) = paths::plugin_dir() {
+        let errors = match plugin::load_dir(&dir) {
+            Ok((_, errors)) => errors,
+            Err(err) => vec![err],
+        };
+
+        for err in errors {
+            Errors::warn_plugin_error(err);
+        }
+    }
+
+    if let Some(command) = args.command {
+        return match command {
+            Command::Stats { path } => commands::stats::run(path.as_deref().map(Path::new)),
+            Command::Boxes { path } => commands::boxes::run(path.as_deref().map(Path::new)),
+            Command::Export {
+                input,
+                format,
+                output,
+                force,
+            } => commands::export::run(
+                Path::new(&input),
+                format,
+                output.as_deref().map(Path::new),
+                force,
+            ),
+            Command::Fmt { paths, check } => commands::fmt::run(&paths, check),
+            Command::Schema => {
+                println!("{}", oxycards::formats::structured::SCHEMA);
+                Ok(())
+            }
+            Command::Import {
+                input,
+                output,
+                force,
+            } => commands::import::run(Path::new(&input), output.as_deref().map(Path::new), force),
+        };
+    }
+
+    let path = Path::new(args.path.as_deref().expect("Required by clap"));
 
     if let Err(err) = Args::validate_file(path) {
         Errors::throw_file_error(err)
     };
 
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(err) => Errors::throw_config_error(err),
+    };
+
+    let file_type = FileType::from_path(path).expect("Checked by validate_file");
+
     let content = read_from_file(path)?;
-    let cards = match Card::card_parser(content) {
-        Ok(cards) => cards,
-        Err(err) => Errors::throw_parsing_error(err),
+    let deck_hash = SavedSession::hash_content(&content);
+    let mut cards = parse_cards(&content, file_type);
+    for card in cards.iter_mut() {
+        if let Card::FlashCard(card) = card {
+            card.orient(args.reverse.map(Reverse::from));
+        }
+    }
+
+    // Only the cards due within their Leitner box are studied
+    let today = Local::now().date_naive();
+    let leitner = if args.leitner {
+        Some(Leitner::load()?)
+    } else {
+        None
     };
+    if let Some(leitner) = &leitner {
+        let next_due = leitner.next_due(path, &cards);
+        cards.retain(|card| leitner.is_due(path, card, today));
+
+        if cards.is_empty() {
+            match next_due {
+                Some(date) => println!(
+                    "No cards are due for review. The next review is on {}.",
+                    date.format("%Y-%m-%d")
+                ),
+                None => println!("No cards are due for review."),
+            }
+            return Ok(());
+        }
+    }
+
+    // Leitner sessions only hold the cards due, while cram sessions queue up
+    // cards again, so neither can be saved and resumed
+    let resumable = !args.leitner && !args.cram;
+
+    let mut app_state = AppState::new(cards, config);
+
+    // A saved session that can't be read shouldn't stop the deck from being studied
+    if let Some(mut saved) = SavedSession::load(path)
+        .unwrap_or(None)
+        .filter(|_| resumable)
+    {
+        let mut cards = parse_cards(&content, file_type);
+
+        // Sessions saved before the deck was edited are discarded
+        if saved.deck_hash == deck_hash && saved.restore(&mut cards) {
+            if prompt_resume(path, &cards)? {
+                app_state = AppState::resume(cards, app_state.config, saved);
+            } else {
+                // The previous session has now ended, so keep a record of it
+                let record = SessionRecord::new(path, saved.started_at, saved.elapsed, &cards);
+                if !record.is_empty() {
+                    History::append(&record)?;
+                }
+            }
+        }
+    }
+
+    app_state.review = args.review;
+    app_state.resumable = resumable;
+    if args.cram {
+        app_state.cram = Some(Cram::new(app_state.cards.items.len()));
+    }
+
+    // The interface needs a card to show
+    if app_state.cards.items.is_empty() {
+        Errors::throw_parsing_error(ParsingError::NoCards);
+    }
 
     let mut terminal = init_terminal()?;
 
-    let app_state = AppState::new(cards);
-    let res = run_app(&mut terminal, app_state);
+    let res = run_app(&mut terminal, &mut app_state);
 
     reset_terminal()?;
 
@@ -96,9 +434,65 @@ fn main() -> Result<(), Box<dyn Error>> {
         println!("{:?}", err);
     }
 
+    if let Some(mut leitner) = leitner {
+        leitner.record(path, &app_state.cards.items, today);
+        leitner.save()?;
+    }
+
+    if let Some(cram) = &app_state.cram {
+        print_cram_summary(cram, &app_state.cards.items);
+    }
+
+    let saved = app_state.saved_session(deck_hash);
+    if !resumable {
+        let record = app_state.session_record(path);
+        if !record.is_empty() {
+            History::append(&record)?;
+        }
+    } else if app_state.has_progress() {
+        saved.save(path)?;
+    } else {
+        SavedSession::remove(path)?;
+
+        // Only sessions where at least one card was answered are worth recording
+        let record = app_state.session_record(path);
+        if !record.is_empty() {
+            History::append(&record)?;
+        }
+    }
+
     Ok(())
 }
 
+/// Print the number of attempts each card took to answer correctly.
+fn print_cram_summary(cram: &Cram, cards: &[Card]) {
+    let mut attempts: Vec<(&str, usize)> = cram
+        .attempts
+        .iter()
+        .enumerate()
+        .filter(|(_, attempts)| **attempts > 0)
+        .filter_map(|(origin, attempts)| {
+            let index = cram.origins.iter().position(|o| *o == origin)?;
+            Some((cards[index].as_card_type().question(), *attempts))
+        })
+        .collect();
+
+    if attempts.is_empty() {
+        return;
+    }
+
+    attempts.sort_by_key(|(_, attempts)| std::cmp::Reverse(*attempts));
+
+    println!(
+        "Crammed {} card(s) in {} attempt(s)",
+        attempts.len(),
+        attempts.iter().map(|(_, attempts)| attempts).sum::<usize>()
+    );
+    for (question, attempts) in attempts {
+        println!("  {:>3}  {}", attempts, question);
+    }
+}
+
 /// Initializes the terminal.
 fn init_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>, Box<dyn Error>> {
     execute!(io::stdout(), EnterAlternateScreen)?;
@@ -135,183 +529,225 @@ fn reset_terminal() -> Result<(), Box<dyn Error>> {
 
 fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
-    mut app_state: AppState,
+    app_state: &mut AppState,
 ) -> Result<(), Box<dyn Error>> {
+    app_state.update_input_mode();
+
     loop {
-        terminal.draw(|f| ui(f, &mut app_state))?;
+        terminal.draw(|f| ui(f, app_state))?;
 
-        if let Some(val) = app_state.cards.selected_value() {
-            match val {
-                Card::FillInTheBlanks(card) => {
-                    if let UserAnswer::Undecided = card.user_answer {
-                        app_state.input_mode = InputMode::Editing
-                    }
+        if let Event::Key(key) = event::read()? {
+            let actions = app_state.config.keys.actions(&key, app_state.key_context());
+
+            // Keys not bound to an action are typed into the selected blank or
+            // search query
+            if actions.is_empty() {
+                match app_state.key_context() {
+                    KeyContext::Editing => edit_card(app_state, &key),
+                    KeyContext::Search => edit_query(app_state, &key),
+                    _ => {}
+                }
+
+                continue;
+            }
+
+            for action in actions {
+                if handle_action(app_state, action) {
+                    return Ok(());
                 }
-                _ => app_state.input_mode = InputMode::Normal,
             }
         }
+    }
+}
 
-        if let Event::Key(key) = event::read()? {
-            match app_state.input_mode {
-                InputMode::Normal => match key.code {
-                    // Card navigation keys
-                    KeyCode::Char('h') | KeyCode::Left => app_state.cards.previous(),
-                    KeyCode::Char('l') | KeyCode::Right => app_state.cards.next(),
-
-                    KeyCode::Char(' ') => {
-                        if let Some(val) = app_state.cards.selected_value() {
-                            match val {
-                                Card::FlashCard(card) => card.show_back(),
-                                Card::MultipleAnswer(card) => {
-                                    if let UserAnswer::Undecided = card.user_answer {
-                                        if let Some(index) = card.choices.selected() {
-                                            card.choices.items[index].select()
-                                        }
-                                    }
-                                }
-                                Card::MultipleChoice(card) => {
-                                    if let Some(index) = card.choices.selected() {
-                                        if let UserAnswer::Undecided = card.user_answer {
-                                            card.unselect_all();
-
-                                            card.choices.items[index].select()
-                                        }
-                                    }
-                                }
-                                Card::Order(card) => {
-                                    if let UserAnswer::Undecided = card.user_answer {
-                                        if let Some(index) = card.shuffled.selected() {
-                                            card.shuffled.items[index].select()
-                                        }
-
-                                        if let Some((a, b)) = card.multiple_selected() {
-                                            card.shuffled.swap(a, b);
-                                            card.unselect_all();
-                                        }
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                    }
+/// Perform an action on the current card.
+///
+/// Returns `true` if the application should exit.
+fn handle_action(app_state: &mut AppState, action: Action) -> bool {
+    if app_state.show_help {
+        match action {
+            Action::Up => app_state.help_scroll = app_state.help_scroll.saturating_sub(1),
+            Action::Down => app_state.help_scroll += 1,
+            Action::Help | Action::Cancel => app_state.show_help = false,
+            _ => {}
+        }
 
-                    KeyCode::Char('k') | KeyCode::Up => {
-                        if let Some(val) = app_state.cards.selected_value() {
-                            match val {
-                                Card::MultipleChoice(card) => card.choices.previous(),
-                                Card::MultipleAnswer(card) => card.choices.previous(),
-                                Card::Order(card) => card.shuffled.previous(),
-                                _ => {}
-                            }
-                        }
-                    }
-                    KeyCode::Char('j') | KeyCode::Down => {
-                        if let Some(val) = app_state.cards.selected_value() {
-                            match val {
-                                Card::MultipleChoice(card) => card.choices.next(),
-                                Card::MultipleAnswer(card) => card.choices.next(),
-                                Card::Order(card) => card.shuffled.next(),
-                                _ => {}
-                            }
-                        }
-                    }
-                    KeyCode::Enter => {
-                        if let Some(card) = app_state.cards.selected_value() {
-                            if !card.check_answered() {
-                                match card.validate_answer() {
-                                    UserAnswer::Correct => app_state.score.add_correct(),
-                                    UserAnswer::Incorrect => app_state.score.add_incorrect(),
-                                    UserAnswer::Undecided => {}
-                                }
-                            }
-                        }
-                    }
-                    KeyCode::Char('y') => {
-                        if let Some(val) = app_state.cards.selected_value() {
-                            match val {
-                                Card::FlashCard(card) => {
-                                    if card.show_validation_popup
-                                        && card.user_answer == UserAnswer::Undecided
-                                    {
-                                        card.user_answer = UserAnswer::Correct;
-                                        app_state.score.add_correct()
-                                    }
-                                }
-
-                                _ => {}
-                            }
-                        }
-                    }
-                    KeyCode::Char('n') => {
-                        if let Some(val) = app_state.cards.selected_value() {
-                            match val {
-                                Card::FlashCard(card) => {
-                                    if card.show_validation_popup
-                                        && card.user_answer == UserAnswer::Undecided
-                                    {
-                                        card.user_answer = UserAnswer::Incorrect;
-                                        app_state.score.add_incorrect()
-                                    }
-                                }
-
-                                _ => {}
-                            }
-                        }
-                    }
+        return false;
+    }
 
-                    // Exit keys
-                    KeyCode::Char('q') => return Ok(()),
+    if app_state.show_overview {
+        let selected = app_state.overview.selected().unwrap_or(0);
+
+        match action {
+            Action::Up => app_state.overview.select(Some(selected.saturating_sub(1))),
+            Action::Down => app_state.overview.select(Some(
+                (selected + 1).min(app_state.cards.items.len().saturating_sub(1)),
+            )),
+            Action::Confirm => {
+                app_state.select_card(selected);
+                app_state.show_overview = false;
+            }
+            Action::Overview | Action::Cancel => app_state.show_overview = false,
+            _ => {}
+        }
 
-                    _ => {}
-                },
-                InputMode::Editing => match key.code {
-                    KeyCode::Tab => {
-                        if let Some(val) = app_state.cards.selected_value() {
-                            match val {
-                                Card::FillInTheBlanks(card) => {
-                                    card.next();
-                                }
-
-                                _ => {}
-                            }
-                        }
-                    }
-                    KeyCode::Enter => {
-                        if let Some(card) = app_state.cards.selected_value() {
-                            if !card.check_answered() {
-                                match card.validate_answer() {
-                                    UserAnswer::Correct => app_state.score.add_correct(),
-                                    UserAnswer::Incorrect => app_state.score.add_incorrect(),
-                                    UserAnswer::Undecided => {}
-                                }
-
-                                app_state.input_mode = InputMode::Normal;
-                            }
-                        }
-                    }
-                    KeyCode::Char(c) => {
-                        if let Some(card_) = app_state.cards.selected_value() {
-                            if let Card::FillInTheBlanks(card) = card_ {
-                                card.user_input[card.blank_index].push(c);
-                                card.update_output();
-                            }
-                        }
-                    }
-                    KeyCode::Backspace => {
-                        if let Some(card_) = app_state.cards.selected_value() {
-                            if let Card::FillInTheBlanks(card) = card_ {
-                                card.user_input[card.blank_index].pop();
-                                card.update_output();
-                            }
-                        }
+        return false;
+    }
+
+    if app_state.show_submit_popup {
+        match action {
+            Action::Confirm => {
+                app_state.submit();
+                app_state.show_submit_popup = false;
+            }
+            Action::Cancel => app_state.show_submit_popup = false,
+            _ => {}
+        }
+
+        return false;
+    }
+
+    if app_state.search.input.is_some() {
+        match action {
+            Action::Confirm => {
+                app_state.search.submit(&app_state.cards.items);
+
+                let current = app_state.cards.selected().unwrap_or(0);
+                if let Some(index) = app_state.search.nearest_match(current) {
+                    app_state.select_card(index);
+                }
+            }
+            Action::Cancel => app_state.search.close(),
+            _ => {}
+        }
+
+        return false;
+    }
+
+    match action {
+        // Card navigation
+        Action::PreviousCard => app_state.previous_card(),
+        Action::NextCard => app_state.next_card(),
+
+        Action::Validate if app_state.submits_deck() => app_state.show_submit_popup = true,
+        Action::Validate => {
+            if let Some(card) = app_state.cards.selected_value() {
+                let card = card.as_card_type_mut();
+                if !card.check_answered() {
+                    card.validate_answer();
+
+                    if card.check_answered() {
+                        app_state.input_mode = InputMode::Normal;
+                        app_state.record_selected_answer();
                     }
-                    KeyCode::Left => app_state.cards.previous(),
-                    KeyCode::Right => app_state.cards.next(),
-                    // Exit keys
-                    KeyCode::Esc => return Ok(()),
-                    _ => {}
-                },
+                }
+            }
+        }
+
+        // Editing cards answered by typing
+        Action::Edit => app_state.update_input_mode(),
+        Action::StopEditing => app_state.input_mode = InputMode::Normal,
+
+        // Actions handled by the selected card
+        Action::Up
+        | Action::Down
+        | Action::Select
+        | Action::Reveal
+        | Action::Undo
+        | Action::Correct
+        | Action::Incorrect
+        | Action::NextBlank
+        | Action::PreviousBlank
+        | Action::CursorLeft
+        | Action::CursorRight
+        | Action::CursorStart
+        | Action::CursorEnd
+        | Action::DeleteChar
+        | Action::DeleteWord => {
+            let graded = app_state.cards.selected_value().is_some_and(|card| {
+                let card = card.as_card_type_mut();
+                let answered = card.check_answered();
+                card.handle_action(action);
+                !answered && card.check_answered()
+            });
+
+            // Cards graded by the user are answered through an action
+            if graded {
+                app_state.record_selected_answer();
+            }
+        }
+
+        // Exit keys
+        Action::Quit => {
+            if !app_state.has_progress() {
+                return true;
+            }
+
+            app_state.show_quit_popup = true;
+        }
+        Action::Confirm => return true,
+        Action::Cancel => app_state.show_quit_popup = false,
+
+        Action::Help => {
+            app_state.show_help = true;
+            app_state.help_scroll = 0;
+        }
+        Action::Overview => {
+            app_state.show_overview = true;
+            app_state.overview.select(app_state.cards.selected());
+        }
+
+        // Searching
+        Action::Search => app_state.search.open(),
+        Action::NextMatch => {
+            let current = app_state.cards.selected().unwrap_or(0);
+            if let Some(index) = app_state.search.next_match(current) {
+                app_state.select_card(index);
+            }
+        }
+        Action::PreviousMatch => {
+            let current = app_state.cards.selected().unwrap_or(0);
+            if let Some(index) = app_state.search.previous_match(current) {
+                app_state.select_card(index);
+            }
+        }
+    }
+
+    false
+}
+
+/// Type a key press into the search query.
+fn edit_query(app_state: &mut AppState, key: &KeyEvent) {
+    if let Some(input) = app_state.search.input.as_mut() {
+        match key.code {
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                input.push(c)
+            }
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Type a key press into the selected card.
+fn edit_card(app_state: &mut AppState, key: &KeyEvent) {
+    if let Some(card) = app_state.cards.selected_value() {
+        match key.code {
+            KeyCode::Char(c)
+                if !key
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                card.as_card_type_mut().type_char(c)
             }
+            KeyCode::Backspace => card.as_card_type_mut().backspace(),
+            _ => {}
         }
     }
 }
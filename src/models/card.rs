@@ -1,112 +1,247 @@
-use crate::Errors;
 use core::fmt;
 
-use crate::UserAnswer;
-
+#[cfg(feature = "plugins")]
+use super::{card_types::plugin::PluginCard, plugin};
 use super::{
     card_types::{
         fill_in_the_blanks::FillInTheBlanks, flashcard::FlashCard, multiple_answer::MultipleAnswer,
-        multiple_choice::MultipleChoice, order::Order,
+        multiple_choice::MultipleChoice, order::Order, CardType,
     },
     errors::parsing_error::ParsingError,
+    matching::MatchOptions,
 };
 
+#[derive(Clone)]
 pub enum Card {
     FlashCard(FlashCard),
     MultipleChoice(MultipleChoice),
     MultipleAnswer(MultipleAnswer),
     FillInTheBlanks(FillInTheBlanks),
     Order(Order),
+    #[cfg(feature = "plugins")]
+    Plugin(PluginCard),
 }
 
-macro_rules! impl_various {
-    ($($card_variant:ident),*) => {
-        impl Card {
-            pub fn validate_answer(&mut self) -> UserAnswer {
-                match self {
-                    $(Card::$card_variant(card) => card.validate_answer()),*
-                }
-            }
+/// Parses the section following a card's type into the card.
+type Parser = fn(String) -> Result<Card, ParsingError>;
 
-            pub fn check_answered(&mut self) -> bool {
-                match self {
-                    $(Card::$card_variant(card) => card.user_answer != UserAnswer::Undecided),*
-                }
-            }
+/// Every card type, by the name it's written as within a deck.
+const CARD_TYPES: &[(&str, Parser)] = &[
+    (FlashCard::NAME, |body| {
+        Ok(Card::FlashCard(FlashCard::parse_raw(body)?))
+    }),
+    (MultipleAnswer::NAME, |body| {
+        Ok(Card::MultipleAnswer(MultipleAnswer::parse_raw(body)?))
+    }),
+    (MultipleChoice::NAME, |body| {
+        Ok(Card::MultipleChoice(MultipleChoice::parse_raw(body)?))
+    }),
+    (FillInTheBlanks::NAME, |body| {
+        Ok(Card::FillInTheBlanks(FillInTheBlanks::parse_raw(body)?))
+    }),
+    (Order::NAME, |body| Ok(Card::Order(Order::parse_raw(body)?))),
+];
 
-            pub fn instructions(&self) -> String {
-                match self {
-                    $(Card::$card_variant(card) => card.instructions()),*
-                }
-            }
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_card_type())
+    }
+}
+
+impl Card {
+    /// The card type the card holds, which everything the card does is done
+    /// through.
+    pub fn as_card_type(&self) -> &dyn CardType {
+        match self {
+            Card::FlashCard(card) => card,
+            Card::MultipleChoice(card) => card,
+            Card::MultipleAnswer(card) => card,
+            Card::FillInTheBlanks(card) => card,
+            Card::Order(card) => card,
+            #[cfg(feature = "plugins")]
+            Card::Plugin(card) => card,
+        }
+    }
+
+    pub fn as_card_type_mut(&mut self) -> &mut dyn CardType {
+        match self {
+            Card::FlashCard(card) => card,
+            Card::MultipleChoice(card) => card,
+            Card::MultipleAnswer(card) => card,
+            Card::FillInTheBlanks(card) => card,
+            Card::Order(card) => card,
+            #[cfg(feature = "plugins")]
+            Card::Plugin(card) => card,
         }
+    }
+
+    /// Parse a deck written in Markdown into its cards.
+    pub fn card_parser(content: String) -> Result<Vec<Self>, ParsingError> {
+        let mut sections: Vec<&str> = content.split("---").collect();
 
-        impl fmt::Display for Card {
-            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                match self {
-                    $(Card::$card_variant(card) => write!(f, "{card}")),*
+        // Options within a section at the top of the deck apply to every card
+        let deck_matching = Card::deck_matching(&content)?;
+        if sections
+            .first()
+            .is_some_and(|s| Card::deck_options(s).is_some())
+        {
+            sections.remove(0);
+        }
+
+        let cards = sections
+            .into_iter()
+            .map(|section| {
+                let sections = section
+                    .trim()
+                    .split("\n\n")
+                    .filter(|item| !item.is_empty())
+                    .collect::<Vec<&str>>();
+
+                if sections.is_empty() {
+                    return Err(ParsingError::IncorrectDivider);
                 }
-            }
+
+                // The card type can be followed by options e.g. `fill_in_the_blanks ignore_case`
+                let mut type_line = sections[0].split_whitespace();
+                let card_type = type_line.next().unwrap_or_default().to_lowercase();
+                let body = sections.get(1).ok_or(ParsingError::NoQuestion)?.to_string();
+
+                // Card types from plugins are checked after the built-in ones
+                let mut card = match CARD_TYPES.iter().find(|(name, _)| *name == card_type) {
+                    Some((_, parse)) => parse(body)?,
+                    #[cfg(feature = "plugins")]
+                    None => match plugin::find(&card_type) {
+                        Some(plugin) => Card::Plugin(PluginCard::parse(plugin, body)?),
+                        None => return Err(ParsingError::NoCardType),
+                    },
+                    #[cfg(not(feature = "plugins"))]
+                    None => return Err(ParsingError::NoCardType),
+                };
+
+                card.as_card_type_mut().set_matching(deck_matching);
+                card.apply_options(&type_line.collect::<Vec<&str>>())?;
+
+                Ok(card)
+            })
+            .collect::<Result<Vec<Self>, ParsingError>>()?;
+
+        if cards.is_empty() {
+            return Err(ParsingError::NoCards);
         }
+
+        Ok(cards)
+    }
+
+    /// Check if a card type is built into oxycards rather than a plugin.
+    pub fn is_built_in(name: &str) -> bool {
+        CARD_TYPES.iter().any(|(card_type, _)| *card_type == name)
+    }
+
+    /// The card written as oxycards Markdown, which parses back into the same
+    /// card.
+    pub fn to_markdown(&self) -> Result<String, ParsingError> {
+        self.write(&MatchOptions::default())
     }
-}
 
-impl_various!(
-    FlashCard,
-    MultipleAnswer,
-    MultipleChoice,
-    FillInTheBlanks,
-    Order
-);
-
-macro_rules! parse_cards {
-    ($(($card_variant:ident, $card_type:expr)),*) => {
-        impl Card {
-            pub fn card_parser(content: String) -> Result<Vec<Self>, ParsingError> {
-                let cards: Vec<Card> = content
-                    .split("---")
-                    .map(|section| {
-                        let sections = section
-                            .trim()
-                            .split("\n\n")
-                            .filter(|item| !item.is_empty())
-                            .collect::<Vec<&str>>();
-
-                        if sections.is_empty() {
-                            Errors::throw_parsing_error(ParsingError::IncorrectDivider)
-                        }
-
-                        match sections[0].to_lowercase().as_str() {
-                            $($card_type => Card::$card_variant(
-                                $card_variant::parse_raw(sections[1].to_string()).unwrap_or_else(
-                                    |err| {
-                                        Errors::throw_parsing_error(err)
-                                    },
-                                ),
-                            )),*,
-                            _ => {
-                                Errors::throw_parsing_error(ParsingError::NoCardType)
-                            }
-                        }
-                    })
-                    .collect();
-
-                Ok(cards)
+    /// Write the card, leaving out options already set for the whole deck.
+    ///
+    /// Cards read from other formats can hold text a Markdown deck can't, such
+    /// as a blank line or `---` within an answer, so the card is read back to
+    /// check that nothing would be lost.
+    fn write(&self, deck_matching: &MatchOptions) -> Result<String, ParsingError> {
+        let options = self.as_card_type().options(deck_matching);
+
+        let mut type_line = String::from(self.as_card_type().name());
+        for option in options {
+            type_line.push(' ');
+            type_line.push_str(&option);
+        }
+
+        let written = format!("{}\n\n{}", type_line, self.as_card_type().to_markdown());
+
+        // Whitespace around each part of a card isn't kept
+        let text = |card: &Card| {
+            let mut text: Vec<String> = card
+                .as_card_type()
+                .search_text()
+                .iter()
+                .map(|text| text.trim().to_string())
+                .collect();
+            text.sort();
+            text
+        };
+
+        match Card::card_parser(written.clone()).as_deref() {
+            Ok([card]) if text(card) == text(self) => Ok(written),
+            _ => Err(ParsingError::Unwritable(
+                self.as_card_type().question().to_string(),
+            )),
+        }
+    }
+
+    /// Write a deck of cards as oxycards Markdown, the reverse of
+    /// [`Card::card_parser`]. Options set for the whole deck are written within
+    /// an `options` section.
+    pub fn write_deck(
+        cards: &[Card],
+        deck_matching: &MatchOptions,
+    ) -> Result<String, ParsingError> {
+        let mut sections = Vec::new();
+
+        let deck_options = deck_matching.names();
+        if !deck_options.is_empty() {
+            sections.push(format!("options {}", deck_options.join(" ")));
+        }
+
+        for card in cards {
+            sections.push(card.write(deck_matching)?);
+        }
+
+        Ok(format!("{}\n", sections.join("\n\n---\n\n")))
+    }
+
+    /// The options set within the deck's `options` section, if it has one.
+    pub fn deck_matching(content: &str) -> Result<MatchOptions, ParsingError> {
+        let mut matching = MatchOptions::default();
+
+        if let Some(options) = content.split("---").next().and_then(Card::deck_options) {
+            for option in options {
+                matching.set(option)?;
             }
         }
-    };
-}
 
-parse_cards!(
-    (FlashCard, "flashcard"),
-    (MultipleAnswer, "multiple_answer"),
-    (MultipleChoice, "multiple_choice"),
-    (FillInTheBlanks, "fill_in_the_blanks"),
-    (Order, "order")
-);
+        Ok(matching)
+    }
+
+    /// The options listed within a deck's `options` section, if the section is
+    /// one.
+    fn deck_options(section: &str) -> Option<Vec<&str>> {
+        let mut words = section.split_whitespace();
 
-impl Card {
-    pub fn extract_card_title(content: &String) -> Result<(String, String), ParsingError> {
+        match words.next() {
+            Some(word) if word.eq_ignore_ascii_case("options") => Some(words.collect()),
+            _ => None,
+        }
+    }
+
+    /// A copy of the card as it was before being answered, used to study the
+    /// card again.
+    pub fn fresh(&self) -> Card {
+        let mut card = self.clone();
+        card.as_card_type_mut().reset();
+        card
+    }
+
+    /// Apply the options written after a card's type.
+    pub fn apply_options(&mut self, options: &[&str]) -> Result<(), ParsingError> {
+        for option in options {
+            self.as_card_type_mut().set_option(option)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn extract_card_title(content: &str) -> Result<(String, String), ParsingError> {
         let question = match content.lines().nth(0) {
             Some(val) => {
                 if val.is_empty() {
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use super::user_answer::UserAnswer;
+
+/// The progress made on a single card, saved and restored by its card type.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CardState {
+    FlashCard {
+        flipped: bool,
+        #[serde(default)]
+        reversed: bool,
+        user_answer: UserAnswer,
+    },
+    MultipleChoice {
+        selected: Vec<bool>,
+        user_answer: UserAnswer,
+    },
+    MultipleAnswer {
+        selected: Vec<bool>,
+        user_answer: UserAnswer,
+    },
+    FillInTheBlanks {
+        user_input: Vec<String>,
+        blank_index: usize,
+        user_answer: UserAnswer,
+    },
+    Order {
+        /// Items in the order the user has currently placed them
+        shuffled: Vec<String>,
+        /// Items in the order they were first shown
+        #[serde(default)]
+        start: Vec<String>,
+        user_answer: UserAnswer,
+    },
+    Plugin {
+        user_input: String,
+        user_answer: UserAnswer,
+    },
+}
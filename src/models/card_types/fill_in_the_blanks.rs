@@ -2,17 +2,31 @@ use core::fmt;
 use regex::Regex;
 use std::collections::HashMap;
 
+#[cfg(feature = "tui")]
+use tui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Paragraph, Widget, Wrap},
+};
+
+#[cfg(feature = "tui")]
+use crate::models::{
+    card_types::CardView,
+    keybindings::{Action, KeyBindings, KeyContext},
+    theme::Highlight,
+};
 use crate::{
-    models::{card::Card, errors::parsing_error::ParsingError},
+    formats::structured::DeckCard,
+    models::{
+        card::Card, card_state::CardState, card_types::CardType,
+        errors::parsing_error::ParsingError, matching::MatchOptions,
+    },
     UserAnswer,
 };
 
-#[derive(Debug)]
-pub struct Answer {
-    pub answers: Vec<String>,
-    pub content: String,
-}
-
+#[derive(Clone)]
 pub struct FillInTheBlanks {
     pub question: String,
     pub content: String,
@@ -20,73 +34,189 @@ pub struct FillInTheBlanks {
     pub user_input: Vec<String>,
     pub answers: HashMap<usize, Vec<String>>,
     pub blank_index: usize,
+    /// Position of the cursor within the selected blank, in characters
+    pub cursor: usize,
+    pub matching: MatchOptions,
+    /// The regex answers compiled for `matching`
+    regexes: HashMap<String, Regex>,
     pub user_answer: UserAnswer,
 }
 
 impl FillInTheBlanks {
-    pub fn check_answered(&self) -> bool {
-        false
-    }
+    pub const NAME: &'static str = "fill_in_the_blanks";
 
+    /// Parse the card from its question and content, the section following
+    /// its card type.
     pub fn parse_raw(content: String) -> Result<Self, ParsingError> {
         let (question, content) = Card::extract_card_title(&content)?;
+        FillInTheBlanks::new(question, content)
+    }
+
+    /// Create the card from its question and the text holding its blanks, e.g.
+    /// `The sky is _blue_`.
+    pub fn new(question: String, content: String) -> Result<Self, ParsingError> {
+        if question.trim().is_empty() {
+            return Err(ParsingError::NoQuestion);
+        }
+
         let re = Regex::new(r"_(.*?)_").expect("Error with regex string.");
 
-        let answers = HashMap::from(
-            re.captures_iter(content.as_ref())
-                .enumerate()
-                .map(|(index, c)| {
-                    let capture: Vec<String> =
-                        c[1].split("|").map(|item| item.to_string()).collect();
+        let answers = re
+            .captures_iter(content.as_ref())
+            .enumerate()
+            .map(|(index, c)| {
+                let capture: Vec<String> = c[1].split("|").map(|item| item.to_string()).collect();
+
+                (index, capture)
+            })
+            .collect::<HashMap<usize, Vec<String>>>();
+
+        // Moving between and typing into blanks needs at least one of them
+        if answers.is_empty() {
+            return Err(ParsingError::NoBlanks);
+        }
 
-                    (index, capture)
-                })
-                .collect::<HashMap<usize, Vec<String>>>(),
-        );
+        for answer in answers.values().flatten() {
+            MatchOptions::check_answer(answer)?;
+        }
 
         // Create an array with empty string of size answers
         let user_input: Vec<String> = answers.iter().map(|_| String::new()).collect();
 
-        Ok(Self {
+        let mut card = Self {
             question,
             content: re.replace_all(content.as_ref(), "__").to_string(),
             answers,
             output: re.replace_all(content.as_ref(), "_").to_string(),
             user_input,
             blank_index: 0,
+            cursor: 0,
+            matching: MatchOptions::default(),
+            regexes: HashMap::new(),
             user_answer: UserAnswer::Undecided,
-        })
+        };
+        card.compile_regexes();
+
+        Ok(card)
+    }
+
+    /// Compile the regex answers again whenever the matching options change.
+    fn compile_regexes(&mut self) {
+        self.regexes = self.matching.compile(self.answers.values().flatten());
+    }
+
+    /// The content with each blank's answers written back in, e.g. `_AUS|Australia_`.
+    pub fn raw_content(&self) -> String {
+        // Each blank is held as `__` within the content, in order
+        let mut content = String::new();
+        for (index, part) in self.content.split("__").enumerate() {
+            if index > 0 {
+                let answers = self.answers.get(&(index - 1)).cloned().unwrap_or_default();
+                content.push_str(&format!("_{}_", answers.join("|")));
+            }
+            content.push_str(part);
+        }
+
+        content
     }
 
     /// Move to the next fill-in-the-blank spot
     pub fn next(&mut self) {
         self.blank_index = (self.blank_index + 1) % self.answers.len();
+        self.cursor_end();
     }
 
-    pub fn instructions(&self) -> String {
-        String::from("<ESC>: Quit application, <TAB>: Cycle selection, <Char>: Add character pressed to blank space")
+    /// Move to the previous fill-in-the-blank spot
+    pub fn previous(&mut self) {
+        self.blank_index = (self.blank_index + self.answers.len() - 1) % self.answers.len();
+        self.cursor_end();
     }
 
-    pub fn validate_answer(&mut self) -> UserAnswer {
-        self.user_answer = UserAnswer::Correct;
+    /// The text typed into the selected blank.
+    pub fn current_input(&self) -> &str {
+        &self.user_input[self.blank_index]
+    }
 
-        if self
-            .user_input
-            .iter()
-            .filter(|item| !item.is_empty())
-            .collect::<Vec<&String>>()
-            .is_empty()
-        {
-            self.user_answer = UserAnswer::Undecided;
+    /// Byte offset of a character position within the selected blank.
+    fn byte_index(&self, position: usize) -> usize {
+        let input = self.current_input();
+
+        input
+            .char_indices()
+            .nth(position)
+            .map_or(input.len(), |(i, _)| i)
+    }
+
+    pub fn cursor_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn cursor_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.current_input().chars().count());
+    }
+
+    pub fn cursor_start(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn cursor_end(&mut self) {
+        self.cursor = self.current_input().chars().count();
+    }
+
+    /// Insert a character at the cursor.
+    pub fn insert(&mut self, c: char) {
+        let index = self.byte_index(self.cursor);
+        self.user_input[self.blank_index].insert(index, c);
+        self.cursor += 1;
+        self.update_output();
+    }
+
+    /// Remove the character before the cursor.
+    pub fn delete_backward(&mut self) {
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.delete_char();
         }
+    }
 
-        for (index, item) in self.user_input.iter().enumerate() {
-            if !self.answers.get(&index).unwrap_or(&vec![]).contains(item) && !item.is_empty() {
-                self.user_answer = UserAnswer::Incorrect;
-            }
+    /// Remove the character under the cursor.
+    pub fn delete_char(&mut self) {
+        let index = self.byte_index(self.cursor);
+
+        if index < self.current_input().len() {
+            self.user_input[self.blank_index].remove(index);
+            self.update_output();
         }
+    }
 
-        self.user_answer
+    /// Remove the word before the cursor, along with any whitespace between
+    /// the word and the cursor.
+    pub fn delete_word(&mut self) {
+        let chars: Vec<char> = self.current_input().chars().take(self.cursor).collect();
+        let mut start = chars.len();
+
+        while start > 0 && chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+        while start > 0 && !chars[start - 1].is_whitespace() {
+            start -= 1;
+        }
+
+        let range = self.byte_index(start)..self.byte_index(self.cursor);
+        self.user_input[self.blank_index].replace_range(range, "");
+        self.cursor = start;
+        self.update_output();
+    }
+
+    /// Check if the text typed into a blank matches any of its answers.
+    pub fn is_correct(&self, index: usize) -> bool {
+        let input = self.user_input.get(index).map_or("", String::as_str);
+
+        self.answers.get(&index).is_some_and(|answers| {
+            answers
+                .iter()
+                .any(|a| self.matching.is_match(a, &self.regexes, input))
+        })
     }
 
     pub fn update_output(&mut self) {
@@ -109,6 +239,252 @@ impl FillInTheBlanks {
     }
 }
 
+#[cfg(feature = "tui")]
+impl FillInTheBlanks {
+    /// The content with a caret drawn at the cursor within the selected blank.
+    fn editing_output(&self) -> Vec<Spans<'_>> {
+        let caret = Style::default().add_modifier(Modifier::REVERSED);
+
+        let new_content = self
+            .content
+            .split("__")
+            .take(self.answers.len())
+            .enumerate()
+            .flat_map(|(index, item)| {
+                let user_content = self.user_input.get(index).map_or("", String::as_str);
+
+                if index != self.blank_index {
+                    return vec![Span::from(item), Span::from(format!("{}_", user_content))];
+                }
+
+                // The caret sits on the blank's trailing underscore once the
+                // cursor is past the end of the input
+                let mut chars = user_content.chars();
+                let before: String = chars.by_ref().take(self.cursor).collect();
+                let (under, after) = match chars.next() {
+                    Some(c) => (c.to_string(), format!("{}_", chars.as_str())),
+                    None => (String::from("_"), String::new()),
+                };
+
+                vec![
+                    Span::from(item),
+                    Span::from(before),
+                    Span::styled(under, caret),
+                    Span::from(after),
+                ]
+            })
+            .collect::<Vec<Span>>();
+
+        vec![Spans::from(new_content)]
+    }
+
+    fn validated_output(&self, view: &CardView) -> Vec<Spans<'_>> {
+        let new_content = self
+            .content
+            .split("__")
+            .take(self.answers.len())
+            .enumerate()
+            .flat_map(|(index, item)| {
+                let user_content = self.user_input.get(index).map_or("", String::as_str);
+
+                let highlight = if self.is_correct(index) {
+                    Highlight::Correct
+                } else {
+                    Highlight::Incorrect
+                };
+
+                let mut spans = vec![Span::from(item), view.styled_span(user_content, highlight)];
+
+                // Reveal the accepted answers next to a wrong blank
+                if highlight == Highlight::Incorrect {
+                    spans.push(Span::styled(
+                        format!(
+                            " ({}{})",
+                            Highlight::Correct.symbol(),
+                            self.answers
+                                .get(&index)
+                                .map_or(String::new(), |a| a.join(" / "))
+                        ),
+                        view.theme.style(Highlight::Correct),
+                    ));
+                }
+
+                spans
+            })
+            .collect::<Vec<Span>>();
+
+        vec![Spans::from(new_content)]
+    }
+}
+
+impl CardType for FillInTheBlanks {
+    fn name(&self) -> &str {
+        FillInTheBlanks::NAME
+    }
+
+    fn question(&self) -> &str {
+        &self.question
+    }
+
+    fn user_answer(&self) -> UserAnswer {
+        self.user_answer
+    }
+
+    fn validate_answer(&mut self) -> UserAnswer {
+        self.user_answer = UserAnswer::Correct;
+
+        if self
+            .user_input
+            .iter()
+            .filter(|item| !item.is_empty())
+            .collect::<Vec<&String>>()
+            .is_empty()
+        {
+            self.user_answer = UserAnswer::Undecided;
+        }
+
+        for (index, item) in self.user_input.iter().enumerate() {
+            if !self.is_correct(index) && !item.is_empty() {
+                self.user_answer = UserAnswer::Incorrect;
+            }
+        }
+
+        self.user_answer
+    }
+
+    #[cfg(feature = "tui")]
+    fn instructions(&self, keys: &KeyBindings) -> String {
+        format!(
+            "{}: Edit blanks, {}: Stop editing, {}/{}: Cycle selection, <Char>: Add character pressed to blank space",
+            keys.describe(Action::Edit, KeyContext::Normal),
+            keys.describe(Action::StopEditing, KeyContext::Editing),
+            keys.describe(Action::NextBlank, KeyContext::Editing),
+            keys.describe(Action::PreviousBlank, KeyContext::Editing)
+        )
+    }
+
+    fn to_markdown(&self) -> String {
+        format!("# {}\n{}", self.question, self.raw_content())
+    }
+
+    fn to_deck_card(&self) -> DeckCard {
+        DeckCard::FillInTheBlanks {
+            question: self.question.clone(),
+            content: self.raw_content(),
+            options: self.matching.names(),
+        }
+    }
+
+    fn search_text(&self) -> Vec<&str> {
+        let mut text = vec![self.question.as_str(), self.content.as_str()];
+        text.extend(self.answers.values().flatten().map(String::as_str));
+        text
+    }
+
+    fn set_option(&mut self, option: &str) -> Result<(), ParsingError> {
+        self.matching.set(option)?;
+        self.compile_regexes();
+
+        Ok(())
+    }
+
+    fn options(&self, deck_matching: &MatchOptions) -> Vec<String> {
+        let deck_options = deck_matching.names();
+
+        self.matching
+            .names()
+            .into_iter()
+            .filter(|option| !deck_options.contains(option))
+            .collect()
+    }
+
+    fn set_matching(&mut self, matching: MatchOptions) {
+        self.matching = matching;
+        self.compile_regexes();
+    }
+
+    #[cfg(feature = "tui")]
+    fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::NextBlank => self.next(),
+            Action::PreviousBlank => self.previous(),
+            Action::CursorLeft => self.cursor_left(),
+            Action::CursorRight => self.cursor_right(),
+            Action::CursorStart => self.cursor_start(),
+            Action::CursorEnd => self.cursor_end(),
+            Action::DeleteChar => self.delete_char(),
+            Action::DeleteWord => self.delete_word(),
+            _ => {}
+        }
+    }
+
+    fn takes_text(&self) -> bool {
+        self.user_answer == UserAnswer::Undecided
+    }
+
+    fn type_char(&mut self, c: char) {
+        self.insert(c);
+    }
+
+    fn backspace(&mut self) {
+        self.delete_backward();
+    }
+
+    fn reset(&mut self) {
+        self.user_input.iter_mut().for_each(String::clear);
+        self.blank_index = 0;
+        self.cursor = 0;
+        self.user_answer = UserAnswer::Undecided;
+        self.update_output();
+    }
+
+    fn has_progress(&self) -> bool {
+        self.user_input.iter().any(|input| !input.is_empty()) || self.check_answered()
+    }
+
+    fn state(&self) -> CardState {
+        CardState::FillInTheBlanks {
+            user_input: self.user_input.clone(),
+            blank_index: self.blank_index,
+            user_answer: self.user_answer,
+        }
+    }
+
+    fn restore(&mut self, state: CardState) -> bool {
+        let CardState::FillInTheBlanks {
+            user_input,
+            blank_index,
+            user_answer,
+        } = state
+        else {
+            return false;
+        };
+
+        if user_input.len() != self.user_input.len() || blank_index >= user_input.len() {
+            return false;
+        }
+        self.user_input = user_input;
+        self.blank_index = blank_index;
+        self.cursor_end();
+        self.user_answer = user_answer;
+        self.update_output();
+        true
+    }
+
+    #[cfg(feature = "tui")]
+    fn render(&mut self, area: Rect, buf: &mut Buffer, view: &CardView) {
+        Paragraph::new(match self.user_answer {
+            UserAnswer::Undecided if view.editing => self.editing_output(),
+            UserAnswer::Undecided => vec![Spans::from(self.output.to_string())],
+            _ => self.validated_output(view),
+        })
+        .block(view.block("Content"))
+        .wrap(Wrap { trim: false })
+        .alignment(Alignment::Center)
+        .render(area, buf);
+    }
+}
+
 impl fmt::Display for FillInTheBlanks {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -1,40 +1,110 @@
 use core::fmt;
 
-use crate::{models::errors::parsing_error::ParsingError, Card, UserAnswer};
+#[cfg(feature = "tui")]
+use tui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    widgets::{Paragraph, Widget, Wrap},
+};
 
+#[cfg(feature = "tui")]
+use crate::models::{
+    card_types::CardView,
+    keybindings::{Action, KeyBindings, KeyContext},
+};
+use crate::{
+    formats::structured::DeckCard,
+    models::{
+        card_state::CardState, card_types::CardType, errors::parsing_error::ParsingError,
+        matching::MatchOptions,
+    },
+    Card, UserAnswer,
+};
+
+/// Which flashcards are studied back-to-front.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Reverse {
+    /// Every flashcard
+    Always,
+    /// Each flashcard either way at random
+    Random,
+}
+
+#[derive(Clone)]
 pub struct FlashCard {
     pub question: String,
     pub answer: String,
     pub flipped: bool,
+    /// Can the card be studied back-to-front
+    pub reversible: bool,
+    /// Is the answer shown as the prompt, with the question hidden
+    pub reversed: bool,
     pub show_validation_popup: bool,
     /// Has the card been validated/answered
     pub user_answer: UserAnswer,
 }
 
 impl FlashCard {
-    pub fn instructions(&self) -> String {
-        String::from("<SPACE>: Show cards back")
-    }
-
-    pub fn validate_answer(&mut self) -> UserAnswer {
-        self.show_validation_popup = !self.show_validation_popup;
-
-        UserAnswer::Undecided
-    }
+    pub const NAME: &'static str = "flashcard";
 
+    /// Parse the card from its question and content, the section following
+    /// its card type.
     pub fn parse_raw(content: String) -> Result<Self, ParsingError> {
         let (question, content) = Card::extract_card_title(&content)?;
+        FlashCard::new(question, content)
+    }
+
+    /// Create the card from its question and answer, neither of which can be
+    /// blank.
+    pub fn new(question: String, answer: String) -> Result<Self, ParsingError> {
+        if question.trim().is_empty() {
+            return Err(ParsingError::NoQuestion);
+        }
+
+        if answer.trim().is_empty() {
+            return Err(ParsingError::NoContent);
+        }
 
         Ok(Self {
             question,
-            answer: content,
+            answer,
             flipped: false,
+            reversible: false,
+            reversed: false,
 
             show_validation_popup: false,
             user_answer: UserAnswer::Undecided,
         })
     }
 
+    /// Pick which side of the card is shown first. Reversible cards are shown
+    /// either way at random unless the side is picked for every card.
+    pub fn orient(&mut self, reverse: Option<Reverse>) {
+        self.reversed = match reverse {
+            Some(Reverse::Always) => true,
+            Some(Reverse::Random) => rand::random(),
+            None => self.reversible && rand::random(),
+        };
+    }
+
+    /// The side of the card shown first.
+    pub fn front(&self) -> &str {
+        if self.reversed {
+            &self.answer
+        } else {
+            &self.question
+        }
+    }
+
+    /// The side of the card hidden until it's flipped.
+    pub fn back(&self) -> &str {
+        if self.reversed {
+            &self.question
+        } else {
+            &self.answer
+        }
+    }
+
     /// Flip card over to show the back.
     pub fn show_back(&mut self) {
         self.flipped = true;
@@ -46,6 +116,140 @@ impl FlashCard {
     }
 }
 
+impl CardType for FlashCard {
+    fn name(&self) -> &str {
+        FlashCard::NAME
+    }
+
+    fn question(&self) -> &str {
+        &self.question
+    }
+
+    fn prompt(&self) -> &str {
+        self.front()
+    }
+
+    fn user_answer(&self) -> UserAnswer {
+        self.user_answer
+    }
+
+    fn validate_answer(&mut self) -> UserAnswer {
+        self.show_validation_popup = !self.show_validation_popup;
+
+        UserAnswer::Undecided
+    }
+
+    #[cfg(feature = "tui")]
+    fn instructions(&self, keys: &KeyBindings) -> String {
+        format!(
+            "{}: Show cards back",
+            keys.describe(Action::Reveal, KeyContext::Normal)
+        )
+    }
+
+    fn to_markdown(&self) -> String {
+        format!("# {}\n{}", self.question, self.answer)
+    }
+
+    fn to_deck_card(&self) -> DeckCard {
+        DeckCard::FlashCard {
+            question: self.question.clone(),
+            answer: self.answer.clone(),
+            reversible: self.reversible,
+        }
+    }
+
+    fn search_text(&self) -> Vec<&str> {
+        vec![&self.question, &self.answer]
+    }
+
+    fn set_option(&mut self, option: &str) -> Result<(), ParsingError> {
+        if !option.eq_ignore_ascii_case("reversible") {
+            return Err(ParsingError::UnknownOption(option.to_string()));
+        }
+
+        self.reversible = true;
+        Ok(())
+    }
+
+    fn options(&self, _deck_matching: &MatchOptions) -> Vec<String> {
+        if self.reversible {
+            vec![String::from("reversible")]
+        } else {
+            Vec::new()
+        }
+    }
+
+    #[cfg(feature = "tui")]
+    fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Reveal => self.show_back(),
+            Action::Correct | Action::Incorrect if self.awaiting_grade() => {
+                self.user_answer = if action == Action::Correct {
+                    UserAnswer::Correct
+                } else {
+                    UserAnswer::Incorrect
+                };
+                self.show_validation_popup = false;
+            }
+            _ => {}
+        }
+    }
+
+    fn self_graded(&self) -> bool {
+        true
+    }
+
+    fn awaiting_grade(&self) -> bool {
+        self.show_validation_popup && self.user_answer == UserAnswer::Undecided
+    }
+
+    fn reset(&mut self) {
+        self.flipped = false;
+        self.show_validation_popup = false;
+        self.user_answer = UserAnswer::Undecided;
+    }
+
+    fn has_progress(&self) -> bool {
+        self.flipped || self.check_answered()
+    }
+
+    fn state(&self) -> CardState {
+        CardState::FlashCard {
+            flipped: self.flipped,
+            reversed: self.reversed,
+            user_answer: self.user_answer,
+        }
+    }
+
+    fn restore(&mut self, state: CardState) -> bool {
+        let CardState::FlashCard {
+            flipped,
+            reversed,
+            user_answer,
+        } = state
+        else {
+            return false;
+        };
+
+        self.flipped = flipped;
+        self.reversed = reversed;
+        self.user_answer = user_answer;
+        true
+    }
+
+    #[cfg(feature = "tui")]
+    fn render(&mut self, area: Rect, buf: &mut Buffer, view: &CardView) {
+        let answer = if self.flipped { self.back() } else { "" };
+
+        Paragraph::new(answer.to_string())
+            .block(view.block("Answer"))
+            .wrap(Wrap { trim: false })
+            .alignment(Alignment::Center)
+            .render(area, buf);
+    }
+}
+
 impl fmt::Display for FlashCard {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Question: {}\nAnswer: {}", self.question, self.answer)
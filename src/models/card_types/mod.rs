@@ -1,5 +1,152 @@
+use core::fmt;
+
+#[cfg(feature = "tui")]
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::Span,
+    widgets::{Block, Borders},
+};
+
+#[cfg(feature = "tui")]
+use crate::models::{
+    keybindings::{Action, KeyBindings},
+    theme::{Highlight, Theme},
+};
+use crate::{
+    formats::structured::DeckCard,
+    models::{card_state::CardState, errors::parsing_error::ParsingError, matching::MatchOptions},
+    UserAnswer,
+};
+
 pub mod fill_in_the_blanks;
 pub mod flashcard;
 pub mod multiple_answer;
 pub mod multiple_choice;
 pub mod order;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+
+/// Everything a card type does once parsed out of a deck, from being drawn
+/// and answered within the terminal to being saved and written back out.
+pub trait CardType: fmt::Display {
+    /// The name the card type is written as within a deck.
+    fn name(&self) -> &str;
+
+    fn question(&self) -> &str;
+
+    /// The text shown above the card, its question unless the card says
+    /// otherwise.
+    fn prompt(&self) -> &str {
+        self.question()
+    }
+
+    fn user_answer(&self) -> UserAnswer;
+
+    fn check_answered(&self) -> bool {
+        self.user_answer() != UserAnswer::Undecided
+    }
+
+    /// Validate the users current answer
+    fn validate_answer(&mut self) -> UserAnswer;
+
+    #[cfg(feature = "tui")]
+    fn instructions(&self, keys: &KeyBindings) -> String;
+
+    /// The card written as Markdown, without its card type.
+    fn to_markdown(&self) -> String;
+
+    /// The card with a field for each of its parts, as written to JSON or
+    /// TOML.
+    fn to_deck_card(&self) -> DeckCard;
+
+    /// Every piece of text held within the card, looked through when
+    /// searching the deck.
+    fn search_text(&self) -> Vec<&str>;
+
+    /// Turn on an option written after the card's type.
+    fn set_option(&mut self, option: &str) -> Result<(), ParsingError> {
+        Err(ParsingError::UnknownOption(option.to_string()))
+    }
+
+    /// The options to write after the card's type, leaving out those already
+    /// set for the whole deck.
+    fn options(&self, _deck_matching: &MatchOptions) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Use the answer matching options set for the whole deck.
+    fn set_matching(&mut self, _matching: MatchOptions) {}
+
+    /// Respond to an action taken while the card is selected.
+    #[cfg(feature = "tui")]
+    fn handle_action(&mut self, _action: Action) {}
+
+    /// Check if the card is currently answered by typing, in which case key
+    /// presses are sent to [`CardType::type_char`].
+    fn takes_text(&self) -> bool {
+        false
+    }
+
+    /// Type a character into the card.
+    fn type_char(&mut self, _c: char) {}
+
+    /// Remove the character before the cursor.
+    fn backspace(&mut self) {}
+
+    /// Check if the card is graded by the user instead of being validated.
+    fn self_graded(&self) -> bool {
+        false
+    }
+
+    /// Check if the card is waiting on the user to grade it.
+    fn awaiting_grade(&self) -> bool {
+        false
+    }
+
+    /// Put the card back the way it was before being answered.
+    fn reset(&mut self);
+
+    /// Check if the card has been answered or partially answered.
+    fn has_progress(&self) -> bool {
+        self.check_answered()
+    }
+
+    /// The progress made on the card, saved when quitting.
+    fn state(&self) -> CardState;
+
+    /// Apply saved progress onto the freshly parsed card.
+    ///
+    /// Returns `false` if the state does not belong to the card.
+    fn restore(&mut self, state: CardState) -> bool;
+
+    /// Draw the card's answer area.
+    #[cfg(feature = "tui")]
+    fn render(&mut self, area: Rect, buf: &mut Buffer, view: &CardView);
+}
+
+/// What a card is drawn with.
+#[cfg(feature = "tui")]
+pub struct CardView<'a> {
+    pub theme: &'a Theme,
+    /// Is the user typing into the card
+    pub editing: bool,
+}
+
+#[cfg(feature = "tui")]
+impl CardView<'_> {
+    /// A bordered block around part of the card.
+    pub fn block(&self, title: &str) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title.to_string())
+    }
+
+    /// A span styled by what it represents.
+    pub fn styled_span(&self, content: &str, highlight: Highlight) -> Span<'static> {
+        Span::styled(
+            format!("{}{}", highlight.symbol(), content),
+            self.theme.style(highlight),
+        )
+    }
+}
@@ -1,23 +1,109 @@
 use core::fmt;
 
+#[cfg(feature = "tui")]
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{List, ListItem, StatefulWidget},
+};
+
+#[cfg(feature = "tui")]
+use crate::models::{
+    card_types::CardView,
+    keybindings::{Action, KeyBindings, KeyContext},
+    theme::Highlight,
+};
 use crate::{
-    models::{choice::Choice, errors::parsing_error::ParsingError, stateful_list::StatefulList},
+    formats::structured::DeckCard,
+    models::{
+        card_state::CardState, card_types::CardType, choice::Choice,
+        errors::parsing_error::ParsingError, stateful_list::StatefulList,
+    },
     Card, UserAnswer,
 };
 
+#[derive(Clone)]
 pub struct MultipleAnswer {
     pub question: String,
     pub choices: StatefulList<Choice>,
     pub answers: Vec<String>,
+    /// Earlier states of the choices, most recent last, used to undo changes
+    pub history: Vec<Vec<Choice>>,
     pub user_answer: UserAnswer,
 }
 
 impl MultipleAnswer {
-    pub fn instructions(&self) -> String {
-        String::from("<SPACE>: Select/unselect choice")
+    pub const NAME: &'static str = "multiple_answer";
+
+    /// Parse the card from its question and content, the section following
+    /// its card type.
+    pub fn parse_raw(content: String) -> Result<Self, ParsingError> {
+        let (question, content) = Card::extract_card_title(&content)?;
+        let choices = MultipleAnswer::remove_prefix(vec![' ', '*'], &content);
+        let answers = MultipleAnswer::remove_prefix(vec!['*'], &content);
+
+        MultipleAnswer::new(question, choices, answers)
+    }
+
+    /// Create the card from its question, choices and correct choices, each of
+    /// which must be one of the choices.
+    pub fn new(
+        question: String,
+        choices: Vec<String>,
+        answers: Vec<String>,
+    ) -> Result<Self, ParsingError> {
+        if question.trim().is_empty() {
+            return Err(ParsingError::NoQuestion);
+        }
+
+        if choices.is_empty() {
+            return Err(ParsingError::NoContent);
+        }
+
+        Choice::check_unique(&choices)?;
+        let answers = Choice::order_answers(&choices, answers)?;
+
+        Ok(Self {
+            question,
+            choices: StatefulList::with_items(
+                choices
+                    .into_iter()
+                    .map(|content| Choice {
+                        content,
+                        selected: false,
+                    })
+                    .collect(),
+            ),
+            answers,
+            history: Vec::new(),
+            user_answer: UserAnswer::Undecided,
+        })
+    }
+
+    /// Remove prefix (* | -) from item
+    fn remove_prefix(prefix: Vec<char>, content: &str) -> Vec<String> {
+        content
+            .lines()
+            .filter(|item| item.chars().nth(1).is_some_and(|c| prefix.contains(&c)))
+            .map(|item| item.get(3..).unwrap_or_default().trim().to_string())
+            .collect()
     }
+}
 
-    pub fn validate_answer(&mut self) -> UserAnswer {
+impl CardType for MultipleAnswer {
+    fn name(&self) -> &str {
+        MultipleAnswer::NAME
+    }
+
+    fn question(&self) -> &str {
+        &self.question
+    }
+
+    fn user_answer(&self) -> UserAnswer {
+        self.user_answer
+    }
+
+    fn validate_answer(&mut self) -> UserAnswer {
         let choices = self
             .choices
             .items
@@ -37,32 +123,134 @@ impl MultipleAnswer {
         self.user_answer
     }
 
-    pub fn parse_raw(content: String) -> Result<Self, ParsingError> {
-        let (question, content) = Card::extract_card_title(&content)?;
+    #[cfg(feature = "tui")]
+    fn instructions(&self, keys: &KeyBindings) -> String {
+        format!(
+            "{}: Select/unselect choice, {}: Undo",
+            keys.describe(Action::Select, KeyContext::Normal),
+            keys.describe(Action::Undo, KeyContext::Normal)
+        )
+    }
 
-        Ok(Self {
-            question,
-            choices: StatefulList::with_items(
-                MultipleAnswer::remove_prefix(vec![' ', '*'], &content)
-                    .iter()
-                    .map(|choice| Choice {
-                        content: choice.clone(),
-                        selected: false,
-                    })
-                    .collect(),
-            ),
-            answers: MultipleAnswer::remove_prefix(vec!['*'], &content),
-            user_answer: UserAnswer::Undecided,
-        })
+    fn to_markdown(&self) -> String {
+        let choices: Vec<String> = self
+            .choices
+            .items
+            .iter()
+            .map(|choice| {
+                let prefix = if self.answers.contains(&choice.content) {
+                    "[*]"
+                } else {
+                    "[ ]"
+                };
+                format!("{} {}", prefix, choice.content)
+            })
+            .collect();
+
+        format!("# {}\n{}", self.question, choices.join("\n"))
     }
 
-    /// Remove prefix (* | -) from item
-    fn remove_prefix(prefix: Vec<char>, content: &String) -> Vec<String> {
-        content
-            .lines()
-            .filter(|item| prefix.contains(&item.chars().nth(1).unwrap()))
-            .map(|item| item[3..].trim().to_string())
-            .collect()
+    fn to_deck_card(&self) -> DeckCard {
+        DeckCard::MultipleAnswer {
+            question: self.question.clone(),
+            choices: self.choices.contents(),
+            answers: self.answers.clone(),
+        }
+    }
+
+    fn search_text(&self) -> Vec<&str> {
+        let mut text = vec![self.question.as_str()];
+        text.extend(self.choices.items.iter().map(|c| c.content.as_str()));
+        text
+    }
+
+    #[cfg(feature = "tui")]
+    fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Up => self.choices.previous(),
+            Action::Down => self.choices.next(),
+            _ if self.check_answered() => {}
+            Action::Select => {
+                if let Some(index) = self.choices.selected() {
+                    self.history.push(self.choices.items.clone());
+                    self.choices.items[index].select()
+                }
+            }
+            Action::Undo => {
+                if let Some(items) = self.history.pop() {
+                    self.choices.items = items;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn reset(&mut self) {
+        for choice in self.choices.items.iter_mut() {
+            choice.unselect();
+        }
+        self.history.clear();
+        self.user_answer = UserAnswer::Undecided;
+    }
+
+    fn has_progress(&self) -> bool {
+        self.choices.items.iter().any(|c| c.selected) || self.check_answered()
+    }
+
+    fn state(&self) -> CardState {
+        CardState::MultipleAnswer {
+            selected: self.choices.selections(),
+            user_answer: self.user_answer,
+        }
+    }
+
+    fn restore(&mut self, state: CardState) -> bool {
+        let CardState::MultipleAnswer {
+            selected,
+            user_answer,
+        } = state
+        else {
+            return false;
+        };
+
+        if !self.choices.restore_selections(&selected) {
+            return false;
+        }
+        self.user_answer = user_answer;
+        true
+    }
+
+    #[cfg(feature = "tui")]
+    fn render(&mut self, area: Rect, buf: &mut Buffer, view: &CardView) {
+        let choices: Vec<ListItem> = self
+            .choices
+            .items
+            .iter()
+            .map(|choice| match choice.selected {
+                true => ListItem::new(view.styled_span(
+                    format!("[x] {}", choice.content).as_str(),
+                    match self.user_answer {
+                        UserAnswer::Correct => Highlight::Correct,
+                        UserAnswer::Incorrect => Highlight::Incorrect,
+                        UserAnswer::Undecided => Highlight::Text,
+                    },
+                )),
+                false => ListItem::new(view.styled_span(
+                    format!("[ ] {}", choice.content).as_str(),
+                    match self.user_answer {
+                        UserAnswer::Correct if self.answers.contains(&choice.content) => {
+                            Highlight::Correct
+                        }
+                        _ => Highlight::Text,
+                    },
+                )),
+            })
+            .collect();
+
+        List::new(choices)
+            .block(view.block("Choices"))
+            .highlight_symbol("> ")
+            .render(area, buf, &mut self.choices.list_state());
     }
 }
 
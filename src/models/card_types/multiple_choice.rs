@@ -1,70 +1,95 @@
 use core::fmt;
 
+#[cfg(feature = "tui")]
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{List, ListItem, StatefulWidget},
+};
+
+#[cfg(feature = "tui")]
+use crate::models::{
+    card_types::CardView,
+    keybindings::{Action, KeyBindings, KeyContext},
+    theme::Highlight,
+};
 use crate::{
-    models::{choice::Choice, errors::parsing_error::ParsingError, stateful_list::StatefulList},
+    formats::structured::DeckCard,
+    models::{
+        card_state::CardState, card_types::CardType, choice::Choice,
+        errors::parsing_error::ParsingError, stateful_list::StatefulList,
+    },
     Card, UserAnswer,
 };
 
+#[derive(Clone)]
 pub struct MultipleChoice {
     pub question: String,
     pub choices: StatefulList<Choice>,
     pub answers: Vec<String>,
 
+    /// Earlier states of the choices, most recent last, used to undo changes
+    pub history: Vec<Vec<Choice>>,
     pub user_answer: UserAnswer,
 }
 
 impl MultipleChoice {
-    /// Validate the users current answer
-    pub fn validate_answer(&mut self) -> UserAnswer {
-        let choices = self
-            .choices
-            .items
-            .iter()
-            .filter(|item| item.selected)
-            .map(|item| item.content.to_string())
-            .collect::<Vec<String>>();
+    pub const NAME: &'static str = "multiple_choice";
 
-        self.user_answer = if choices.is_empty() {
-            UserAnswer::Undecided
-        } else if choices == self.answers {
-            UserAnswer::Correct
-        } else {
-            UserAnswer::Incorrect
-        };
+    /// Parse the card from its question and content, the section following
+    /// its card type.
+    pub fn parse_raw(content: String) -> Result<Self, ParsingError> {
+        let (question, content) = Card::extract_card_title(&content)?;
+        let choices = MultipleChoice::remove_prefix(vec!['-', '*'], &content);
+        let answers = MultipleChoice::remove_prefix(vec!['*'], &content);
 
-        self.user_answer
+        MultipleChoice::new(question, choices, answers)
     }
 
-    pub fn instructions(&self) -> String {
-        String::from("<SPACE>: Select/unselect choice")
-    }
+    /// Create the card from its question, choices and correct choice, which
+    /// must be one of the choices.
+    pub fn new(
+        question: String,
+        choices: Vec<String>,
+        answers: Vec<String>,
+    ) -> Result<Self, ParsingError> {
+        if question.trim().is_empty() {
+            return Err(ParsingError::NoQuestion);
+        }
 
-    pub fn parse_raw(content: String) -> Result<Self, ParsingError> {
-        let (question, content) = Card::extract_card_title(&content)?;
+        if choices.is_empty() {
+            return Err(ParsingError::NoContent);
+        }
+
+        Choice::check_unique(&choices)?;
+        let answers = Choice::order_answers(&choices, answers)?;
+
+        if answers.len() != 1 {
+            return Err(ParsingError::AnswerCount(answers.len()));
+        }
 
         Ok(Self {
             question,
             choices: StatefulList::with_items(
-                MultipleChoice::remove_prefix(vec!['-', '*'], &content)
-                    .iter()
-                    .map(|choice| Choice {
-                        // Todo: maybe don't clone?
-                        content: choice.clone(),
+                choices
+                    .into_iter()
+                    .map(|content| Choice {
+                        content,
                         selected: false,
                     })
                     .collect(),
             ),
-            answers: MultipleChoice::remove_prefix(vec!['*'], &content),
+            answers,
+            history: Vec::new(),
             user_answer: UserAnswer::Undecided,
         })
     }
 
     /// Remove prefix (* | -) from item
-    fn remove_prefix(prefix: Vec<char>, content: &String) -> Vec<String> {
+    fn remove_prefix(prefix: Vec<char>, content: &str) -> Vec<String> {
         content
             .lines()
-            // Todo: Don't unwrap
-            .filter(|item| prefix.contains(&item.chars().nth(0).unwrap()))
+            .filter(|item| item.chars().next().is_some_and(|c| prefix.contains(&c)))
             .map(|item| item[1..].trim().to_string())
             .collect()
     }
@@ -77,6 +102,170 @@ impl MultipleChoice {
     }
 }
 
+impl CardType for MultipleChoice {
+    fn name(&self) -> &str {
+        MultipleChoice::NAME
+    }
+
+    fn question(&self) -> &str {
+        &self.question
+    }
+
+    fn user_answer(&self) -> UserAnswer {
+        self.user_answer
+    }
+
+    /// Validate the users current answer
+    fn validate_answer(&mut self) -> UserAnswer {
+        let choices = self
+            .choices
+            .items
+            .iter()
+            .filter(|item| item.selected)
+            .map(|item| item.content.to_string())
+            .collect::<Vec<String>>();
+
+        self.user_answer = if choices.is_empty() {
+            UserAnswer::Undecided
+        } else if choices == self.answers {
+            UserAnswer::Correct
+        } else {
+            UserAnswer::Incorrect
+        };
+
+        self.user_answer
+    }
+
+    #[cfg(feature = "tui")]
+    fn instructions(&self, keys: &KeyBindings) -> String {
+        format!(
+            "{}: Select/unselect choice, {}: Undo",
+            keys.describe(Action::Select, KeyContext::Normal),
+            keys.describe(Action::Undo, KeyContext::Normal)
+        )
+    }
+
+    fn to_markdown(&self) -> String {
+        let choices: Vec<String> = self
+            .choices
+            .items
+            .iter()
+            .map(|choice| {
+                let prefix = if self.answers.contains(&choice.content) {
+                    '*'
+                } else {
+                    '-'
+                };
+                format!("{} {}", prefix, choice.content)
+            })
+            .collect();
+
+        format!("# {}\n{}", self.question, choices.join("\n"))
+    }
+
+    fn to_deck_card(&self) -> DeckCard {
+        DeckCard::MultipleChoice {
+            question: self.question.clone(),
+            choices: self.choices.contents(),
+            answers: self.answers.clone(),
+        }
+    }
+
+    fn search_text(&self) -> Vec<&str> {
+        let mut text = vec![self.question.as_str()];
+        text.extend(self.choices.items.iter().map(|c| c.content.as_str()));
+        text
+    }
+
+    #[cfg(feature = "tui")]
+    fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Up => self.choices.previous(),
+            Action::Down => self.choices.next(),
+            _ if self.check_answered() => {}
+            Action::Select => {
+                if let Some(index) = self.choices.selected() {
+                    self.history.push(self.choices.items.clone());
+                    self.unselect_all();
+
+                    self.choices.items[index].select()
+                }
+            }
+            Action::Undo => {
+                if let Some(items) = self.history.pop() {
+                    self.choices.items = items;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn reset(&mut self) {
+        self.unselect_all();
+        self.history.clear();
+        self.user_answer = UserAnswer::Undecided;
+    }
+
+    fn has_progress(&self) -> bool {
+        self.choices.items.iter().any(|c| c.selected) || self.check_answered()
+    }
+
+    fn state(&self) -> CardState {
+        CardState::MultipleChoice {
+            selected: self.choices.selections(),
+            user_answer: self.user_answer,
+        }
+    }
+
+    fn restore(&mut self, state: CardState) -> bool {
+        let CardState::MultipleChoice {
+            selected,
+            user_answer,
+        } = state
+        else {
+            return false;
+        };
+
+        if !self.choices.restore_selections(&selected) {
+            return false;
+        }
+        self.user_answer = user_answer;
+        true
+    }
+
+    #[cfg(feature = "tui")]
+    fn render(&mut self, area: Rect, buf: &mut Buffer, view: &CardView) {
+        let choices: Vec<ListItem> = self
+            .choices
+            .items
+            .iter()
+            .map(|choice| {
+                ListItem::new(view.styled_span(
+                    choice.content.as_ref(),
+                    match choice.selected {
+                        true => match self.user_answer {
+                            UserAnswer::Correct => Highlight::Correct,
+                            UserAnswer::Incorrect => Highlight::Incorrect,
+                            UserAnswer::Undecided => Highlight::Selected,
+                        },
+                        false => match self.user_answer {
+                            UserAnswer::Incorrect if self.answers.contains(&choice.content) => {
+                                Highlight::Correct
+                            }
+                            _ => Highlight::Text,
+                        },
+                    },
+                ))
+            })
+            .collect();
+
+        List::new(choices)
+            .block(view.block("Choices"))
+            .highlight_symbol("> ")
+            .render(area, buf, &mut self.choices.list_state());
+    }
+}
+
 impl fmt::Display for MultipleChoice {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -2,65 +2,95 @@ use core::fmt;
 
 use rand::seq::SliceRandom;
 
+#[cfg(feature = "tui")]
+use tui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Span, Spans},
+    widgets::{List, ListItem, StatefulWidget},
+};
+
+#[cfg(feature = "tui")]
+use crate::models::{
+    card_types::CardView,
+    keybindings::{Action, KeyBindings, KeyContext},
+    theme::Highlight,
+};
 use crate::{
-    models::{choice::Choice, errors::parsing_error::ParsingError, stateful_list::StatefulList},
+    formats::structured::DeckCard,
+    models::{
+        card_state::CardState, card_types::CardType, choice::Choice,
+        errors::parsing_error::ParsingError, stateful_list::StatefulList,
+    },
     Card, UserAnswer,
 };
 
+#[derive(Clone)]
 pub struct Order {
     pub question: String,
     pub shuffled: StatefulList<Choice>,
     pub answer: Vec<String>,
+    /// Items in the order they were first shown, used to tell if the user has
+    /// moved any
+    pub start: Vec<String>,
 
+    /// Earlier states of the choices, most recent last, used to undo changes
+    pub history: Vec<Vec<Choice>>,
     pub user_answer: UserAnswer,
 }
 
 impl Order {
-    pub fn instructions(&self) -> String {
-        return String::from(
-            "<SPACE>: Select item, use <Space> again on another item to swap them",
-        );
-    }
-
-    pub fn validate_answer(&mut self) -> UserAnswer {
-        let choices = self
-            .shuffled
-            .items
-            .iter()
-            .map(|item| item.content.to_string())
-            .collect::<Vec<String>>();
+    pub const NAME: &'static str = "order";
 
-        self.user_answer = if choices == self.answer {
-            UserAnswer::Correct
-        } else {
-            UserAnswer::Incorrect
-        };
+    /// Parse the card from its question and content, the section following
+    /// its card type.
+    pub fn parse_raw(content: String) -> Result<Self, ParsingError> {
+        let (question, content) = Card::extract_card_title(&content)?;
+        let answer = content.lines().map(Order::remove_number).collect();
 
-        self.user_answer
+        Order::new(question, answer)
     }
 
-    pub fn parse_raw(content: String) -> Result<Self, ParsingError> {
-        let (question, content) = Card::extract_card_title(&content)?;
-        let mut rng = rand::thread_rng();
+    /// Create the card from its question and items in the correct order, shown
+    /// shuffled.
+    pub fn new(question: String, answer: Vec<String>) -> Result<Self, ParsingError> {
+        if question.trim().is_empty() {
+            return Err(ParsingError::NoQuestion);
+        }
 
-        let mut shuffled: Vec<Choice> = content
-            .lines()
-            .map(|line| Choice {
-                content: line[3..].to_string(),
+        if answer.is_empty() {
+            return Err(ParsingError::NoContent);
+        }
+
+        let mut shuffled: Vec<Choice> = answer
+            .iter()
+            .map(|item| Choice {
+                content: item.clone(),
                 selected: false,
             })
             .collect();
 
-        shuffled.shuffle(&mut rng);
+        shuffled.shuffle(&mut rand::thread_rng());
+        let start = shuffled.iter().map(|item| item.content.clone()).collect();
 
         Ok(Self {
             question,
             shuffled: StatefulList::with_items(shuffled),
-            answer: content.lines().map(|line| line[3..].to_string()).collect(),
+            answer,
+            start,
+            history: Vec::new(),
             user_answer: UserAnswer::Undecided,
         })
     }
 
+    /// Remove the number from a list item, e.g. `10. item`
+    fn remove_number(line: &str) -> String {
+        let item = line
+            .trim_start()
+            .trim_start_matches(|c: char| c.is_ascii_digit());
+        item.strip_prefix('.').unwrap_or(item).trim().to_string()
+    }
+
     /// Check if there are multiple items currently selected
     pub fn multiple_selected(&self) -> Option<(usize, usize)> {
         let selected: Vec<i32> = self
@@ -79,6 +109,16 @@ impl Order {
         }
     }
 
+    /// Check if `items` holds the same items as the card, in any order.
+    pub fn is_permutation(&self, items: &[String]) -> bool {
+        let mut items = items.to_vec();
+        let mut answer = self.answer.clone();
+        items.sort();
+        answer.sort();
+
+        items == answer
+    }
+
     /// Unselect all items held within the internal vector
     pub fn unselect_all(&mut self) {
         for choice in self.shuffled.items.iter_mut() {
@@ -87,6 +127,196 @@ impl Order {
     }
 }
 
+impl CardType for Order {
+    fn name(&self) -> &str {
+        Order::NAME
+    }
+
+    fn question(&self) -> &str {
+        &self.question
+    }
+
+    fn user_answer(&self) -> UserAnswer {
+        self.user_answer
+    }
+
+    fn validate_answer(&mut self) -> UserAnswer {
+        self.user_answer = if self.shuffled.contents() == self.answer {
+            UserAnswer::Correct
+        } else {
+            UserAnswer::Incorrect
+        };
+
+        self.user_answer
+    }
+
+    #[cfg(feature = "tui")]
+    fn instructions(&self, keys: &KeyBindings) -> String {
+        let select = keys.describe(Action::Select, KeyContext::Normal);
+
+        format!(
+            "{}: Select item, use {} again on another item to swap them, {}: Undo",
+            select,
+            select,
+            keys.describe(Action::Undo, KeyContext::Normal)
+        )
+    }
+
+    /// The card written as Markdown, without its card type. Items are written
+    /// in their correct order.
+    fn to_markdown(&self) -> String {
+        let items: Vec<String> = self
+            .answer
+            .iter()
+            .enumerate()
+            .map(|(i, item)| format!("{}. {}", i + 1, item))
+            .collect();
+
+        format!("# {}\n{}", self.question, items.join("\n"))
+    }
+
+    fn to_deck_card(&self) -> DeckCard {
+        DeckCard::Order {
+            question: self.question.clone(),
+            answer: self.answer.clone(),
+        }
+    }
+
+    fn search_text(&self) -> Vec<&str> {
+        let mut text = vec![self.question.as_str()];
+        text.extend(self.answer.iter().map(String::as_str));
+        text
+    }
+
+    #[cfg(feature = "tui")]
+    fn handle_action(&mut self, action: Action) {
+        match action {
+            Action::Up => self.shuffled.previous(),
+            Action::Down => self.shuffled.next(),
+            _ if self.check_answered() => {}
+            Action::Select => {
+                self.history.push(self.shuffled.items.clone());
+
+                if let Some(index) = self.shuffled.selected() {
+                    self.shuffled.items[index].select()
+                }
+
+                if let Some((a, b)) = self.multiple_selected() {
+                    self.shuffled.swap(a, b);
+                    self.unselect_all();
+                }
+            }
+            Action::Undo => {
+                if let Some(items) = self.history.pop() {
+                    self.shuffled.items = items;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn reset(&mut self) {
+        self.unselect_all();
+        self.shuffled.items.shuffle(&mut rand::thread_rng());
+        self.start = self.shuffled.contents();
+        self.history.clear();
+        self.user_answer = UserAnswer::Undecided;
+    }
+
+    /// Moving the items counts as progress, unless they're moved back to where
+    /// they started.
+    fn has_progress(&self) -> bool {
+        self.shuffled.contents() != self.start || self.check_answered()
+    }
+
+    fn state(&self) -> CardState {
+        CardState::Order {
+            shuffled: self.shuffled.contents(),
+            start: self.start.clone(),
+            user_answer: self.user_answer,
+        }
+    }
+
+    fn restore(&mut self, state: CardState) -> bool {
+        let CardState::Order {
+            shuffled,
+            start,
+            user_answer,
+        } = state
+        else {
+            return false;
+        };
+
+        // The saved orders must be permutations of the cards items
+        if !self.is_permutation(&shuffled) || !(start.is_empty() || self.is_permutation(&start)) {
+            return false;
+        }
+
+        // Sessions saved before the starting order was kept start from the
+        // order they were saved in
+        self.start = if start.is_empty() {
+            shuffled.clone()
+        } else {
+            start
+        };
+        self.shuffled.items = shuffled
+            .into_iter()
+            .map(|content| Choice {
+                content,
+                selected: false,
+            })
+            .collect();
+        self.user_answer = user_answer;
+        true
+    }
+
+    #[cfg(feature = "tui")]
+    fn render(&mut self, area: Rect, buf: &mut Buffer, view: &CardView) {
+        let choices: Vec<ListItem> = self
+            .shuffled
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, choice)| match choice.selected {
+                true => ListItem::new(Spans::from(vec![
+                    Span::raw(format!("{}. ", i + 1)),
+                    view.styled_span(choice.content.as_ref(), Highlight::Selected),
+                ])),
+                false => {
+                    let highlight = match self.user_answer {
+                        UserAnswer::Correct => Highlight::Correct,
+                        // Items already in their place are still shown as correct
+                        UserAnswer::Incorrect if self.answer[i] == choice.content => {
+                            Highlight::Correct
+                        }
+                        UserAnswer::Incorrect => Highlight::Incorrect,
+                        UserAnswer::Undecided => Highlight::Text,
+                    };
+
+                    let mut spans = vec![view
+                        .styled_span(format!("{}. {}", i + 1, choice.content).as_ref(), highlight)];
+
+                    // Reveal the item that belongs in this place
+                    if highlight == Highlight::Incorrect {
+                        spans.push(Span::raw(" "));
+                        spans.push(Span::styled(
+                            format!("({}{})", Highlight::Correct.symbol(), self.answer[i]),
+                            view.theme.style(Highlight::Correct),
+                        ));
+                    }
+
+                    ListItem::new(Spans::from(spans))
+                }
+            })
+            .collect();
+
+        List::new(choices)
+            .block(view.block("Choices"))
+            .highlight_symbol("> ")
+            .render(area, buf, &mut self.shuffled.list_state());
+    }
+}
+
 impl fmt::Display for Order {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
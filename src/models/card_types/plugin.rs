@@ -0,0 +1,277 @@
+use core::fmt;
+use std::sync::Arc;
+
+use rhai::Dynamic;
+#[cfg(feature = "tui")]
+use tui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Paragraph, Widget, Wrap},
+};
+
+#[cfg(feature = "tui")]
+use crate::models::{
+    card_types::CardView,
+    keybindings::{Action, KeyBindings, KeyContext},
+    theme::Highlight,
+};
+use crate::{
+    formats::structured::DeckCard,
+    models::{
+        card_state::CardState, card_types::CardType, errors::parsing_error::ParsingError,
+        plugin::CardPlugin,
+    },
+    Card, UserAnswer,
+};
+
+/// A card whose type is defined by a plugin, answered by typing.
+#[derive(Clone)]
+pub struct PluginCard {
+    pub plugin: Arc<CardPlugin>,
+    pub question: String,
+    /// The card's content as written within the deck
+    pub content: String,
+    /// The content once parsed by the plugin
+    pub card: Dynamic,
+    pub user_input: String,
+    /// The last error raised by the plugin's script
+    pub error: Option<String>,
+    pub user_answer: UserAnswer,
+}
+
+impl PluginCard {
+    /// Parse the card through its plugin.
+    pub fn parse(plugin: Arc<CardPlugin>, content: String) -> Result<Self, ParsingError> {
+        let (question, content) = Card::extract_card_title(&content)?;
+        PluginCard::new(plugin, question, content)
+    }
+
+    /// Create the card from its question and content, as read by its plugin.
+    pub fn new(
+        plugin: Arc<CardPlugin>,
+        question: String,
+        content: String,
+    ) -> Result<Self, ParsingError> {
+        if question.trim().is_empty() {
+            return Err(ParsingError::NoQuestion);
+        }
+
+        if content.trim().is_empty() {
+            return Err(ParsingError::NoContent);
+        }
+
+        let card = plugin
+            .parse(&question, &content)
+            .map_err(|err| ParsingError::PluginFailed(plugin.name.clone(), err))?;
+
+        Ok(Self {
+            plugin,
+            question,
+            content,
+            card,
+            user_input: String::new(),
+            error: None,
+            user_answer: UserAnswer::Undecided,
+        })
+    }
+}
+
+#[cfg(feature = "tui")]
+impl PluginCard {
+    /// Remove the word before the end of the input, along with any whitespace
+    /// following it.
+    fn delete_word(&mut self) {
+        let trimmed = self.user_input.trim_end();
+        let start = trimmed
+            .rfind(char::is_whitespace)
+            .map_or(0, |index| index + 1);
+
+        self.user_input.truncate(start);
+    }
+
+    fn answer_line(&self, view: &CardView) -> Spans<'_> {
+        if self.user_answer == UserAnswer::Undecided {
+            let mut spans = vec![Span::raw("> "), Span::raw(self.user_input.as_str())];
+            if view.editing {
+                spans.push(Span::styled(
+                    "_",
+                    Style::default().add_modifier(Modifier::REVERSED),
+                ));
+            }
+
+            return Spans::from(spans);
+        }
+
+        let highlight = if self.user_answer == UserAnswer::Correct {
+            Highlight::Correct
+        } else {
+            Highlight::Incorrect
+        };
+
+        let mut spans = vec![view.styled_span(&self.user_input, highlight)];
+
+        // Reveal the answer next to a wrong one, when the plugin gives it
+        if highlight == Highlight::Incorrect {
+            if let Ok(Some(answer)) = self.plugin.answer(&self.card) {
+                spans.push(Span::styled(
+                    format!(" ({}{})", Highlight::Correct.symbol(), answer),
+                    view.theme.style(Highlight::Correct),
+                ));
+            }
+        }
+
+        Spans::from(spans)
+    }
+}
+
+impl CardType for PluginCard {
+    fn name(&self) -> &str {
+        &self.plugin.name
+    }
+
+    fn question(&self) -> &str {
+        &self.question
+    }
+
+    fn user_answer(&self) -> UserAnswer {
+        self.user_answer
+    }
+
+    fn validate_answer(&mut self) -> UserAnswer {
+        if self.user_input.trim().is_empty() {
+            return self.user_answer;
+        }
+
+        match self.plugin.validate(&self.card, &self.user_input) {
+            Ok(correct) => {
+                self.error = None;
+                self.user_answer = if correct {
+                    UserAnswer::Correct
+                } else {
+                    UserAnswer::Incorrect
+                };
+            }
+            // The card is left unanswered, showing the error
+            Err(err) => self.error = Some(err),
+        }
+
+        self.user_answer
+    }
+
+    #[cfg(feature = "tui")]
+    fn instructions(&self, keys: &KeyBindings) -> String {
+        self.plugin.instructions().unwrap_or_else(|| {
+            format!(
+                "{}: Type answer, {}: Stop editing, <Char>: Add character pressed to answer",
+                keys.describe(Action::Edit, KeyContext::Normal),
+                keys.describe(Action::StopEditing, KeyContext::Editing)
+            )
+        })
+    }
+
+    fn to_markdown(&self) -> String {
+        format!("# {}\n{}", self.question, self.content)
+    }
+
+    fn to_deck_card(&self) -> DeckCard {
+        DeckCard::Plugin {
+            card_type: self.plugin.name.clone(),
+            question: self.question.clone(),
+            content: self.content.clone(),
+        }
+    }
+
+    fn search_text(&self) -> Vec<&str> {
+        vec![&self.question, &self.content]
+    }
+
+    #[cfg(feature = "tui")]
+    fn handle_action(&mut self, action: Action) {
+        if action == Action::DeleteWord {
+            self.delete_word();
+        }
+    }
+
+    fn takes_text(&self) -> bool {
+        self.user_answer == UserAnswer::Undecided
+    }
+
+    fn type_char(&mut self, c: char) {
+        self.user_input.push(c);
+    }
+
+    fn backspace(&mut self) {
+        self.user_input.pop();
+    }
+
+    fn reset(&mut self) {
+        self.user_input.clear();
+        self.error = None;
+        self.user_answer = UserAnswer::Undecided;
+    }
+
+    fn has_progress(&self) -> bool {
+        !self.user_input.is_empty() || self.check_answered()
+    }
+
+    fn state(&self) -> CardState {
+        CardState::Plugin {
+            user_input: self.user_input.clone(),
+            user_answer: self.user_answer,
+        }
+    }
+
+    fn restore(&mut self, state: CardState) -> bool {
+        let CardState::Plugin {
+            user_input,
+            user_answer,
+        } = state
+        else {
+            return false;
+        };
+
+        self.user_input = user_input;
+        self.user_answer = user_answer;
+        true
+    }
+
+    #[cfg(feature = "tui")]
+    fn render(&mut self, area: Rect, buf: &mut Buffer, view: &CardView) {
+        let text = match &self.error {
+            Some(err) => Err(err.clone()),
+            None => self.plugin.render(&self.card),
+        };
+
+        let mut lines: Vec<Spans> = match text {
+            Ok(text) => text
+                .lines()
+                .map(|line| Spans::from(line.to_string()))
+                .collect(),
+            Err(err) => vec![Spans::from(
+                view.styled_span(&format!("Error: {}", err), Highlight::Incorrect),
+            )],
+        };
+        if !lines.is_empty() {
+            lines.push(Spans::default());
+        }
+        lines.push(self.answer_line(view));
+
+        Paragraph::new(lines)
+            .block(view.block("Answer"))
+            .wrap(Wrap { trim: false })
+            .alignment(Alignment::Center)
+            .render(area, buf);
+    }
+}
+
+impl fmt::Display for PluginCard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Question: {}\nCard type: {}\nContent: {:?}",
+            self.question, self.plugin.name, self.content
+        )
+    }
+}
@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use super::{errors::parsing_error::ParsingError, stateful_list::StatefulList};
+
+#[derive(Clone, Debug)]
 pub struct Choice {
     pub content: String,
     pub selected: bool,
@@ -14,4 +16,67 @@ impl Choice {
     pub fn unselect(&mut self) {
         self.selected = false;
     }
+
+    /// Check that no choice is listed twice, as choices are told apart by
+    /// their content.
+    pub fn check_unique(choices: &[String]) -> Result<(), ParsingError> {
+        for (i, choice) in choices.iter().enumerate() {
+            if choices[..i].contains(choice) {
+                return Err(ParsingError::DuplicateChoice(choice.clone()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Put the correct choices into the order they're listed in, as they're
+    /// compared against the choices selected, checking each is one of the
+    /// choices and is listed once.
+    pub fn order_answers(
+        choices: &[String],
+        answers: Vec<String>,
+    ) -> Result<Vec<String>, ParsingError> {
+        for (i, answer) in answers.iter().enumerate() {
+            if !choices.contains(answer) {
+                return Err(ParsingError::UnknownAnswer(answer.clone()));
+            }
+
+            if answers[..i].contains(answer) {
+                return Err(ParsingError::DuplicateAnswer(answer.clone()));
+            }
+        }
+
+        Ok(choices
+            .iter()
+            .filter(|choice| answers.contains(choice))
+            .cloned()
+            .collect())
+    }
+}
+
+impl StatefulList<Choice> {
+    /// The content of every choice, in order.
+    pub fn contents(&self) -> Vec<String> {
+        self.items.iter().map(|c| c.content.clone()).collect()
+    }
+
+    /// Whether each choice is selected, in order.
+    pub fn selections(&self) -> Vec<bool> {
+        self.items.iter().map(|c| c.selected).collect()
+    }
+
+    /// Select the choices as they were saved.
+    ///
+    /// Returns `false` if the number of choices has changed.
+    pub fn restore_selections(&mut self, selected: &[bool]) -> bool {
+        if self.items.len() != selected.len() {
+            return false;
+        }
+
+        for (choice, selected) in self.items.iter_mut().zip(selected) {
+            choice.selected = *selected;
+        }
+
+        true
+    }
 }
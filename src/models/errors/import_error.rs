@@ -0,0 +1,44 @@
+use core::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum ImportError {
+    UnsupportedFormat(String),
+    Read(String),
+    Package(String),
+    OutputExists(String),
+    NoNotes,
+    NoQuestions,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::UnsupportedFormat(path) => write!(
+                f,
+                "Unable to import {}. Supported formats are Anki packages (.apkg, .colpkg), Anki's text export (.txt, .tsv) and Moodle GIFT (.gift)",
+                path
+            ),
+            ImportError::Read(err) => write!(f, "Unable to read file: {}", err),
+            ImportError::Package(err) => write!(f, "Invalid Anki package: {}", err),
+            ImportError::OutputExists(path) => write!(
+                f,
+                "{} already exists. Use --force to overwrite it",
+                path
+            ),
+            ImportError::NoNotes => write!(f, "No Basic or Cloze notes were found to import"),
+            ImportError::NoQuestions => write!(
+                f,
+                "No multiple choice, short answer, numerical or true/false questions were found to import"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl From<io::Error> for ImportError {
+    fn from(err: io::Error) -> Self {
+        ImportError::Read(err.to_string())
+    }
+}
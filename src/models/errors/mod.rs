@@ -1,3 +1,3 @@
-pub mod errors;
-pub mod file_error;
+pub mod import_error;
 pub mod parsing_error;
+pub mod plugin_error;
@@ -1,15 +1,30 @@
 use core::fmt;
 
+#[derive(Debug)]
 pub enum ParsingError {
+    NoCards,
     NoCardType,
     NoQuestion,
     NoContent,
+    NoBlanks,
     IncorrectDivider,
+    UnknownOption(String),
+    InvalidRegex(String),
+    InvalidRow(String),
+    InvalidDeck(String),
+    UnknownAnswer(String),
+    DuplicateChoice(String),
+    DuplicateAnswer(String),
+    AnswerCount(usize),
+    Unwritable(String),
+    IgnoredContent,
+    PluginFailed(String, String),
 }
 
 impl fmt::Display for ParsingError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            ParsingError::NoCards => write!(f, "The deck doesn't hold any cards"),
             ParsingError::NoContent => {
                 write!(f, "No available content to parse for one or more cards.")
             }
@@ -17,9 +32,49 @@ impl fmt::Display for ParsingError {
             ParsingError::NoCardType => {
                 write!(f, "One or more cards have not specified their card type.\nA list of all supported card types can be found here: https://brookjeynes.github.io/quiz-rs/cards")
             }
+            ParsingError::NoBlanks => write!(
+                f,
+                "One or more fill in the blanks cards have no blanks. Blanks are written as _answer_"
+            ),
             ParsingError::IncorrectDivider => {
                 write!(f, "One or more cards have an incorrect divider (---)")
             }
+            ParsingError::UnknownOption(option) => {
+                write!(f, "Unknown card option `{}`", option)
+            }
+            ParsingError::InvalidRegex(answer) => {
+                write!(f, "Invalid regex answer `{}`", answer)
+            }
+            ParsingError::InvalidRow(err) => write!(f, "Invalid row: {}", err),
+            ParsingError::InvalidDeck(err) => write!(f, "Invalid deck: {}", err),
+            ParsingError::IgnoredContent => write!(
+                f,
+                "One or more cards have content after a blank line, which is not part of the card"
+            ),
+            ParsingError::UnknownAnswer(answer) => {
+                write!(f, "The answer `{}` is not one of the card's choices", answer)
+            }
+            ParsingError::DuplicateChoice(choice) => {
+                write!(f, "The choice `{}` is listed more than once within a card", choice)
+            }
+            ParsingError::DuplicateAnswer(answer) => {
+                write!(f, "The answer `{}` is listed more than once within a card", answer)
+            }
+            ParsingError::AnswerCount(count) => write!(
+                f,
+                "Multiple choice cards have exactly one answer, but one has {}",
+                count
+            ),
+            ParsingError::Unwritable(question) => write!(
+                f,
+                "The card `{}` holds text a Markdown deck can't, such as a blank line or `---`",
+                question
+            ),
+            ParsingError::PluginFailed(name, err) => {
+                write!(f, "The `{}` card type plugin could not parse a card: {}", name, err)
+            }
         }
     }
 }
+
+impl std::error::Error for ParsingError {}
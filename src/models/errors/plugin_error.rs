@@ -0,0 +1,38 @@
+use core::fmt;
+
+#[derive(Debug)]
+pub enum PluginError {
+    Unreadable(String, String),
+    InvalidScript(String, String),
+    MissingFunction(String, &'static str),
+    ReservedName(String),
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PluginError::Unreadable(path, err) => {
+                write!(f, "Unable to read the card type plugin {}: {}", path, err)
+            }
+            PluginError::InvalidScript(name, err) => {
+                write!(
+                    f,
+                    "The `{}` card type plugin is not a valid script: {}",
+                    name, err
+                )
+            }
+            PluginError::MissingFunction(name, function) => write!(
+                f,
+                "The `{}` card type plugin must define a `{}` function",
+                name, function
+            ),
+            PluginError::ReservedName(name) => write!(
+                f,
+                "The `{}` card type plugin has the same name as a built-in card type or the `options` section",
+                name
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
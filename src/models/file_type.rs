@@ -1,18 +1,31 @@
 use core::fmt;
 use std::ffi::OsStr;
+use std::path::Path;
 
-#[derive(PartialEq)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum FileType {
     Markdown,
+    Csv,
+    Tsv,
+    Json,
+    Toml,
 }
 
 impl FileType {
+    /// The format of a deck going by its file extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension().and_then(FileType::from_osstr)
+    }
+
     pub fn from_osstr(file_extension: &OsStr) -> Option<Self> {
-        if file_extension == "md" {
-            return Some(FileType::Markdown);
+        match file_extension.to_str()?.to_lowercase().as_str() {
+            "md" => Some(FileType::Markdown),
+            "csv" => Some(FileType::Csv),
+            "tsv" => Some(FileType::Tsv),
+            "json" => Some(FileType::Json),
+            "toml" => Some(FileType::Toml),
+            _ => None,
         }
-
-        None
     }
 }
 
@@ -20,6 +33,10 @@ impl fmt::Display for FileType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             FileType::Markdown => write!(f, "md"),
+            FileType::Csv => write!(f, "csv"),
+            FileType::Tsv => write!(f, "tsv"),
+            FileType::Json => write!(f, "json"),
+            FileType::Toml => write!(f, "toml"),
         }
     }
 }
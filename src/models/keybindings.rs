@@ -0,0 +1,429 @@
+use core::fmt;
+use std::collections::HashMap;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::Deserialize;
+
+/// Everything a user can do with a key press.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    NextCard,
+    PreviousCard,
+    Up,
+    Down,
+    Select,
+    Reveal,
+    Validate,
+    Correct,
+    Incorrect,
+    Edit,
+    StopEditing,
+    NextBlank,
+    PreviousBlank,
+    CursorLeft,
+    CursorRight,
+    CursorStart,
+    CursorEnd,
+    DeleteChar,
+    DeleteWord,
+    Undo,
+    Quit,
+    Confirm,
+    Cancel,
+    Help,
+    Overview,
+    Search,
+    NextMatch,
+    PreviousMatch,
+}
+
+impl Action {
+    /// What the action does, shown within the help overlay.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::NextCard => "Move to the next card",
+            Action::PreviousCard => "Move to the previous card",
+            Action::Up => "Move the selection up",
+            Action::Down => "Move the selection down",
+            Action::Select => "Select/unselect a choice, or swap two items",
+            Action::Reveal => "Show the cards back",
+            Action::Validate => "Validate answer",
+            Action::Correct => "Mark the card as correct",
+            Action::Incorrect => "Mark the card as incorrect",
+            Action::Edit => "Start editing the blanks",
+            Action::StopEditing => "Stop editing the blanks",
+            Action::NextBlank => "Move to the next blank",
+            Action::PreviousBlank => "Move to the previous blank",
+            Action::CursorLeft => "Move the cursor left",
+            Action::CursorRight => "Move the cursor right",
+            Action::CursorStart => "Move the cursor to the start of the blank",
+            Action::CursorEnd => "Move the cursor to the end of the blank",
+            Action::DeleteChar => "Delete the character under the cursor",
+            Action::DeleteWord => "Delete the word before the cursor",
+            Action::Undo => "Undo the last change to the choices",
+            Action::Quit => "Quit application",
+            Action::Confirm => "Confirm",
+            Action::Cancel => "Cancel",
+            Action::Help => "Show/hide this help",
+            Action::Overview => "Show/hide the card overview",
+            Action::Search => {
+                "Search the deck, wrap the query in slashes for a regex e.g. /colou?r/"
+            }
+            Action::NextMatch => "Move to the next search match",
+            Action::PreviousMatch => "Move to the previous search match",
+        }
+    }
+}
+
+/// Where key presses are currently being read from.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyContext {
+    Normal,
+    /// Typing into a fill in the blanks card
+    Editing,
+    /// Marking a flashcard as correct or incorrect
+    Validation,
+    /// Typing a search query
+    Search,
+    /// A popup asking the user to confirm something
+    Popup,
+    /// The help overlay
+    Help,
+    /// The card overview, listing every card
+    Overview,
+}
+
+impl KeyContext {
+    /// Check if plain characters are typed as text within this context.
+    pub fn is_text_input(&self) -> bool {
+        matches!(self, KeyContext::Editing | KeyContext::Search)
+    }
+
+    /// Heading for the context's actions within the help overlay.
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeyContext::Normal => "Studying",
+            KeyContext::Editing => "Typing an answer",
+            KeyContext::Validation => "Flashcard (after validating)",
+            KeyContext::Search => "Search (while typing)",
+            KeyContext::Popup => "Popups",
+            KeyContext::Help => "Help",
+            KeyContext::Overview => "Card overview",
+        }
+    }
+
+    /// Actions that can be triggered within this context.
+    pub fn actions(&self) -> &'static [Action] {
+        match self {
+            KeyContext::Normal => &[
+                Action::NextCard,
+                Action::PreviousCard,
+                Action::Up,
+                Action::Down,
+                Action::Select,
+                Action::Reveal,
+                Action::Validate,
+                Action::Edit,
+                Action::Undo,
+                Action::Quit,
+                Action::Help,
+                Action::Overview,
+                Action::Search,
+                Action::NextMatch,
+                Action::PreviousMatch,
+            ],
+            KeyContext::Editing => &[
+                Action::NextBlank,
+                Action::PreviousBlank,
+                Action::CursorLeft,
+                Action::CursorRight,
+                Action::CursorStart,
+                Action::CursorEnd,
+                Action::DeleteChar,
+                Action::DeleteWord,
+                Action::Validate,
+                Action::StopEditing,
+                Action::Quit,
+                Action::Help,
+            ],
+            KeyContext::Validation => &[
+                Action::Correct,
+                Action::Incorrect,
+                Action::Validate,
+                Action::NextCard,
+                Action::PreviousCard,
+                Action::Quit,
+                Action::Help,
+            ],
+            KeyContext::Search => &[Action::Confirm, Action::Cancel],
+            KeyContext::Popup => &[Action::Confirm, Action::Cancel],
+            KeyContext::Help => &[Action::Up, Action::Down, Action::Help, Action::Cancel],
+            KeyContext::Overview => &[
+                Action::Up,
+                Action::Down,
+                Action::Confirm,
+                Action::Overview,
+                Action::Cancel,
+            ],
+        }
+    }
+}
+
+/// A key press, e.g. `q`, `<ENTER>` or `<Ctrl-c>`.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(try_from = "String")]
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
+}
+
+impl Key {
+    pub fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    pub fn ctrl(c: char) -> Self {
+        Self {
+            code: KeyCode::Char(c),
+            modifiers: KeyModifiers::CONTROL,
+        }
+    }
+
+    /// Check if the key was pressed.
+    ///
+    /// Shift is ignored as it is already part of the character or key code
+    /// e.g. `N` or `<BackTab>`.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        self.code == event.code
+            && self.modifiers - KeyModifiers::SHIFT == event.modifiers - KeyModifiers::SHIFT
+    }
+
+    /// Plain characters are typed into blanks while editing rather than
+    /// triggering an action.
+    pub fn is_plain_char(&self) -> bool {
+        matches!(self.code, KeyCode::Char(_))
+            && !self
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+    }
+}
+
+impl TryFrom<String> for Key {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = || format!("Invalid key `{}`", value);
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = value.as_str();
+
+        // A lone `-` is a key in its own right rather than a separator
+        while let Some((modifier, rest)) = key.split_once('-').filter(|(_, rest)| !rest.is_empty())
+        {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(invalid()),
+            };
+            key = rest;
+        }
+
+        let mut chars = key.chars();
+        let code = match (chars.next(), chars.next()) {
+            (Some(c), None) => KeyCode::Char(c),
+            _ => match key.to_lowercase().as_str() {
+                "space" => KeyCode::Char(' '),
+                "enter" => KeyCode::Enter,
+                "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+                "tab" => KeyCode::Tab,
+                "backtab" => KeyCode::BackTab,
+                "esc" => KeyCode::Esc,
+                "backspace" => KeyCode::Backspace,
+                "delete" | "del" => KeyCode::Delete,
+                "insert" => KeyCode::Insert,
+                "home" => KeyCode::Home,
+                "end" => KeyCode::End,
+                "pageup" => KeyCode::PageUp,
+                "pagedown" => KeyCode::PageDown,
+                "up" => KeyCode::Up,
+                "down" => KeyCode::Down,
+                "left" => KeyCode::Left,
+                "right" => KeyCode::Right,
+                function => match function.strip_prefix('f').map(str::parse) {
+                    Some(Ok(n)) if (1..=12).contains(&n) => KeyCode::F(n),
+                    _ => return Err(invalid()),
+                },
+            },
+        };
+
+        Ok(Self { code, modifiers })
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self.code {
+            KeyCode::Char(' ') => String::from("SPACE"),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => String::from("ENTER"),
+            KeyCode::Tab => String::from("TAB"),
+            KeyCode::BackTab => String::from("Shift-TAB"),
+            KeyCode::Esc => String::from("ESC"),
+            KeyCode::Backspace => String::from("BACKSPACE"),
+            KeyCode::Delete => String::from("DELETE"),
+            KeyCode::Insert => String::from("INSERT"),
+            KeyCode::Home => String::from("HOME"),
+            KeyCode::End => String::from("END"),
+            KeyCode::PageUp => String::from("PAGEUP"),
+            KeyCode::PageDown => String::from("PAGEDOWN"),
+            KeyCode::Up => String::from("UP"),
+            KeyCode::Down => String::from("DOWN"),
+            KeyCode::Left => String::from("LEFT"),
+            KeyCode::Right => String::from("RIGHT"),
+            KeyCode::F(n) => format!("F{}", n),
+            _ => String::from("?"),
+        };
+
+        let mut prefix = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            prefix.push_str("Ctrl-");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            prefix.push_str("Alt-");
+        }
+
+        if prefix.is_empty() && matches!(self.code, KeyCode::Char(c) if c != ' ') {
+            write!(f, "{}", name)
+        } else {
+            write!(f, "<{}{}>", prefix, name)
+        }
+    }
+}
+
+/// The keys bound to each action.
+///
+/// Actions missing from a users config fall back to their default keys.
+#[derive(Deserialize, Debug)]
+#[serde(from = "HashMap<Action, Vec<Key>>")]
+pub struct KeyBindings {
+    bindings: HashMap<Action, Vec<Key>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let bindings = HashMap::from([
+            (
+                Action::NextCard,
+                vec![Key::new(KeyCode::Char('l')), Key::new(KeyCode::Right)],
+            ),
+            (
+                Action::PreviousCard,
+                vec![Key::new(KeyCode::Char('h')), Key::new(KeyCode::Left)],
+            ),
+            (
+                Action::Up,
+                vec![Key::new(KeyCode::Char('k')), Key::new(KeyCode::Up)],
+            ),
+            (
+                Action::Down,
+                vec![Key::new(KeyCode::Char('j')), Key::new(KeyCode::Down)],
+            ),
+            (Action::Select, vec![Key::new(KeyCode::Char(' '))]),
+            (Action::Reveal, vec![Key::new(KeyCode::Char(' '))]),
+            (Action::Validate, vec![Key::new(KeyCode::Enter)]),
+            (Action::Correct, vec![Key::new(KeyCode::Char('y'))]),
+            (Action::Incorrect, vec![Key::new(KeyCode::Char('n'))]),
+            (Action::Edit, vec![Key::new(KeyCode::Char('i'))]),
+            (Action::StopEditing, vec![Key::new(KeyCode::Esc)]),
+            (Action::NextBlank, vec![Key::new(KeyCode::Tab)]),
+            (Action::PreviousBlank, vec![Key::new(KeyCode::BackTab)]),
+            (Action::CursorLeft, vec![Key::new(KeyCode::Left)]),
+            (Action::CursorRight, vec![Key::new(KeyCode::Right)]),
+            (Action::CursorStart, vec![Key::new(KeyCode::Home)]),
+            (Action::CursorEnd, vec![Key::new(KeyCode::End)]),
+            (Action::DeleteChar, vec![Key::new(KeyCode::Delete)]),
+            (Action::DeleteWord, vec![Key::ctrl('w')]),
+            (Action::Undo, vec![Key::new(KeyCode::Char('u'))]),
+            (
+                Action::Quit,
+                vec![Key::new(KeyCode::Char('q')), Key::ctrl('c')],
+            ),
+            (
+                Action::Confirm,
+                vec![Key::new(KeyCode::Char('y')), Key::new(KeyCode::Enter)],
+            ),
+            (
+                Action::Cancel,
+                vec![Key::new(KeyCode::Char('n')), Key::new(KeyCode::Esc)],
+            ),
+            (
+                Action::Help,
+                vec![Key::new(KeyCode::Char('?')), Key::new(KeyCode::F(1))],
+            ),
+            (Action::Overview, vec![Key::new(KeyCode::Char('o'))]),
+            (Action::Search, vec![Key::new(KeyCode::Char('/'))]),
+            (Action::NextMatch, vec![Key::new(KeyCode::Char('n'))]),
+            (Action::PreviousMatch, vec![Key::new(KeyCode::Char('N'))]),
+        ]);
+
+        Self { bindings }
+    }
+}
+
+impl From<HashMap<Action, Vec<Key>>> for KeyBindings {
+    fn from(bindings: HashMap<Action, Vec<Key>>) -> Self {
+        let mut keys = KeyBindings::default();
+        keys.bindings.extend(bindings);
+
+        keys
+    }
+}
+
+impl KeyBindings {
+    /// Keys bound to an action within a context.
+    pub fn keys(&self, action: Action, context: KeyContext) -> Vec<Key> {
+        self.bindings
+            .get(&action)
+            .map(|keys| {
+                keys.iter()
+                    .filter(|key| !context.is_text_input() || !key.is_plain_char())
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Every action within the context bound to the key pressed.
+    pub fn actions(&self, event: &KeyEvent, context: KeyContext) -> Vec<Action> {
+        context
+            .actions()
+            .iter()
+            .filter(|action| {
+                self.keys(**action, context)
+                    .iter()
+                    .any(|key| key.matches(event))
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Describe the keys bound to an action for use within instructions, e.g.
+    /// `h/<LEFT>`.
+    pub fn describe(&self, action: Action, context: KeyContext) -> String {
+        let keys = self.keys(action, context);
+
+        if keys.is_empty() {
+            return String::from("<unbound>");
+        }
+
+        keys.iter()
+            .map(|key| key.to_string())
+            .collect::<Vec<String>>()
+            .join("/")
+    }
+}
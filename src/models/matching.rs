@@ -0,0 +1,297 @@
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+use super::errors::parsing_error::ParsingError;
+
+/// How the text typed into a blank is compared against its answers.
+///
+/// Set per card after the card type e.g. `fill_in_the_blanks ignore_case`, or
+/// for the whole deck within an `options` section at the top of the deck.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MatchOptions {
+    /// `Colour` matches `colour`
+    pub ignore_case: bool,
+    /// Whitespace around the typed text is ignored
+    pub trim: bool,
+    /// `cafe` matches `café`
+    pub fold_accents: bool,
+    /// Answers such as `#3.14:0.01` are numbers
+    pub numeric: bool,
+}
+
+impl MatchOptions {
+    /// Turn on an option by its name.
+    pub fn set(&mut self, option: &str) -> Result<(), ParsingError> {
+        match option.to_lowercase().as_str() {
+            "ignore_case" => self.ignore_case = true,
+            "trim" => self.trim = true,
+            "fold_accents" => self.fold_accents = true,
+            "numeric" => self.numeric = true,
+            _ => return Err(ParsingError::UnknownOption(option.to_string())),
+        }
+
+        Ok(())
+    }
+
+    /// The names of the options turned on, as they're written within a deck.
+    pub fn names(&self) -> Vec<String> {
+        [
+            (self.ignore_case, "ignore_case"),
+            (self.trim, "trim"),
+            (self.fold_accents, "fold_accents"),
+            (self.numeric, "numeric"),
+        ]
+        .into_iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, name)| name.to_string())
+        .collect()
+    }
+
+    /// Compile the regex answers among `answers` with these options, once
+    /// when the card is parsed rather than each time the typed text is checked.
+    pub fn compile<'a>(
+        &self,
+        answers: impl IntoIterator<Item = &'a String>,
+    ) -> HashMap<String, Regex> {
+        answers
+            .into_iter()
+            .filter_map(|answer| {
+                let regex = self.regex(regex_pattern(answer)?).ok()?;
+                Some((answer.clone(), regex))
+            })
+            .collect()
+    }
+
+    /// Check if the text typed matches an answer. Answers surrounded by
+    /// slashes e.g. `/colou?r/` are regexes that must match the whole text,
+    /// looked up within `regexes` as compiled by [`MatchOptions::compile`].
+    /// With the `numeric` option, answers such as `#3.14:0.01` are numbers.
+    pub fn is_match(&self, answer: &str, regexes: &HashMap<String, Regex>, input: &str) -> bool {
+        if let Some((min, max)) = numeric_range(answer).filter(|_| self.numeric) {
+            // Allow for rounding so `3.13` is within `#3.14:0.01`
+            let slack = f64::EPSILON * 4.0 * min.abs().max(max.abs()).max(1.0);
+
+            return input
+                .trim()
+                .parse::<f64>()
+                .is_ok_and(|number| min - slack <= number && number <= max + slack);
+        }
+
+        let input = self.normalise(input);
+
+        match regex_pattern(answer) {
+            Some(_) => regexes
+                .get(answer)
+                .is_some_and(|regex| regex.is_match(&input)),
+            None => self.normalise(answer) == input,
+        }
+    }
+
+    fn normalise(&self, text: &str) -> String {
+        let mut text = if self.trim { text.trim() } else { text }.to_string();
+
+        if self.ignore_case {
+            text = text.to_lowercase();
+        }
+
+        if self.fold_accents {
+            text = text.nfd().filter(|c| !is_combining_mark(*c)).collect();
+        }
+
+        text
+    }
+
+    fn regex(&self, pattern: &str) -> Result<Regex, regex::Error> {
+        RegexBuilder::new(&format!("^(?:{})$", pattern))
+            .case_insensitive(self.ignore_case)
+            .build()
+    }
+
+    /// Check that an answer is valid, i.e. that a regex answer compiles.
+    pub fn check_answer(answer: &str) -> Result<(), ParsingError> {
+        match regex_pattern(answer) {
+            Some(pattern) => MatchOptions::default()
+                .regex(pattern)
+                .map(|_| ())
+                .map_err(|_| ParsingError::InvalidRegex(answer.to_string())),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The pattern of a regex answer, e.g. `colou?r` from `/colou?r/`.
+fn regex_pattern(answer: &str) -> Option<&str> {
+    answer
+        .strip_prefix('/')
+        .and_then(|answer| answer.strip_suffix('/'))
+        .filter(|pattern| !pattern.is_empty())
+}
+
+/// The accepted range of a numeric answer, either `#value`, `#value:tolerance`
+/// or `#min..max`. Only used with the `numeric` option, answers starting with
+/// `#` are otherwise matched as text.
+pub fn numeric_range(answer: &str) -> Option<(f64, f64)> {
+    let answer = answer.strip_prefix('#')?.trim();
+
+    if let Some((min, max)) = answer.split_once("..") {
+        return Some((min.trim().parse().ok()?, max.trim().parse().ok()?));
+    }
+
+    match answer.split_once(':') {
+        Some((value, tolerance)) => {
+            let value: f64 = value.trim().parse().ok()?;
+            let tolerance: f64 = tolerance.trim().parse::<f64>().ok()?.abs();
+
+            Some((value - tolerance, value + tolerance))
+        }
+        None => answer.parse().ok().map(|value| (value, value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(names: &[&str]) -> MatchOptions {
+        let mut options = MatchOptions::default();
+        for name in names {
+            options.set(name).unwrap();
+        }
+        options
+    }
+
+    fn is_match(options: &MatchOptions, answer: &str, input: &str) -> bool {
+        let answers = [answer.to_string()];
+        options.is_match(answer, &options.compile(&answers), input)
+    }
+
+    #[test]
+    fn text_is_matched_exactly_by_default() {
+        let options = MatchOptions::default();
+
+        assert!(is_match(&options, "Colour", "Colour"));
+        assert!(!is_match(&options, "Colour", "colour"));
+        assert!(!is_match(&options, "Colour", " Colour "));
+        assert!(!is_match(&options, "café", "cafe"));
+    }
+
+    #[test]
+    fn ignore_case() {
+        let options = options(&["ignore_case"]);
+
+        assert!(is_match(&options, "Colour", "cOLOUR"));
+        assert!(!is_match(&options, "Colour", "Color"));
+    }
+
+    #[test]
+    fn trim() {
+        let options = options(&["trim"]);
+
+        assert!(is_match(&options, "Colour", "  Colour\t"));
+        assert!(!is_match(&options, "Colour", "Col our"));
+    }
+
+    #[test]
+    fn fold_accents() {
+        let options = options(&["fold_accents"]);
+
+        assert!(is_match(&options, "café", "cafe"));
+        assert!(is_match(&options, "cafe", "café"));
+        assert!(!is_match(&options, "café", "Cafe"));
+    }
+
+    #[test]
+    fn regex_answers_match_the_whole_text() {
+        let options = MatchOptions::default();
+
+        assert!(is_match(&options, "/colou?r/", "color"));
+        assert!(is_match(&options, "/colou?r/", "colour"));
+        assert!(!is_match(&options, "/colou?r/", "colours"));
+        assert!(!is_match(&options, "/colou?r/", "Colour"));
+    }
+
+    #[test]
+    fn regex_answers_with_ignore_case() {
+        let options = options(&["ignore_case"]);
+
+        assert!(is_match(&options, "/Colou?r/", "COLOR"));
+        assert!(is_match(&options, "/[A-Z]+/", "abc"));
+        assert!(!is_match(&options, "/Colou?r/", "colours"));
+    }
+
+    #[test]
+    fn regex_answers_need_compiling() {
+        let options = MatchOptions::default();
+
+        assert!(!options.is_match("/colou?r/", &HashMap::new(), "colour"));
+    }
+
+    #[test]
+    fn numeric_value_with_tolerance() {
+        let options = options(&["numeric"]);
+
+        assert!(is_match(&options, "#3.14:0.01", "3.14"));
+        assert!(is_match(&options, "#3.14:0.01", " 3.149 "));
+        assert!(is_match(&options, "#3.14:0.01", "3.13"));
+        assert!(!is_match(&options, "#3.14:0.01", "3.16"));
+        assert!(!is_match(&options, "#3.14:0.01", "pi"));
+    }
+
+    #[test]
+    fn numeric_range_of_values() {
+        let options = options(&["numeric"]);
+
+        assert!(is_match(&options, "#1..5", "1"));
+        assert!(is_match(&options, "#1..5", "2.5"));
+        assert!(is_match(&options, "#1..5", "5"));
+        assert!(!is_match(&options, "#1..5", "5.01"));
+        assert!(!is_match(&options, "#1..5", "0"));
+    }
+
+    #[test]
+    fn numeric_answers_are_text_without_the_option() {
+        let options = MatchOptions::default();
+
+        assert!(is_match(&options, "#1..5", "#1..5"));
+        assert!(!is_match(&options, "#1..5", "3"));
+    }
+
+    #[test]
+    fn malformed_numeric_answers_are_matched_as_text() {
+        let options = options(&["numeric"]);
+
+        assert_eq!(numeric_range("#three"), None);
+        assert_eq!(numeric_range("#1..five"), None);
+        assert_eq!(numeric_range("#3.14:"), None);
+        assert!(is_match(&options, "#three", "#three"));
+        assert!(!is_match(&options, "#three", "3"));
+    }
+
+    #[test]
+    fn numeric_ranges() {
+        assert_eq!(numeric_range("#2"), Some((2.0, 2.0)));
+        assert_eq!(numeric_range("#10:-2"), Some((8.0, 12.0)));
+        assert_eq!(numeric_range("# 1 .. 5 "), Some((1.0, 5.0)));
+        assert_eq!(numeric_range("2"), None);
+    }
+
+    #[test]
+    fn unknown_options_are_rejected() {
+        assert!(matches!(
+            MatchOptions::default().set("fuzzy"),
+            Err(ParsingError::UnknownOption(option)) if option == "fuzzy"
+        ));
+        assert_eq!(
+            options(&["numeric", "IGNORE_CASE"]).names(),
+            ["ignore_case", "numeric"]
+        );
+    }
+
+    #[test]
+    fn invalid_regex_answers_are_rejected() {
+        assert!(MatchOptions::check_answer("/colou?r/").is_ok());
+        assert!(MatchOptions::check_answer("/(colour/").is_err());
+        assert!(MatchOptions::check_answer("(colour").is_ok());
+    }
+}
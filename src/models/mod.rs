@@ -1,8 +1,15 @@
-pub mod args;
 pub mod card;
+pub mod card_state;
 pub mod card_types;
 pub mod choice;
+pub mod errors;
 pub mod file_type;
+#[cfg(feature = "tui")]
+pub mod keybindings;
+pub mod matching;
+#[cfg(feature = "plugins")]
+pub mod plugin;
 pub mod stateful_list;
+#[cfg(feature = "tui")]
+pub mod theme;
 pub mod user_answer;
-pub mod errors;
@@ -0,0 +1,195 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use rhai::{Dynamic, Engine, FuncArgs, Scope, AST};
+
+use super::{card::Card, errors::plugin_error::PluginError};
+
+/// Operations a script may run for a single call before it's stopped, so a
+/// runaway loop can't freeze the application.
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Card types registered from plugins, looked up by the deck parser.
+static PLUGINS: RwLock<Vec<Arc<CardPlugin>>> = RwLock::new(Vec::new());
+
+/// A card type defined by a Rhai script.
+///
+/// The script must define `validate(card, input)`, returning whether the typed
+/// answer is correct. It can also define:
+///
+/// - `parse(question, content)`, turning the card's content into the `card`
+///   passed to every other function. Defaults to the content itself.
+/// - `render(card)`, the text shown above the answer.
+/// - `answer(card)`, the answer revealed after answering incorrectly.
+/// - `instructions()`, replacing the card's default instructions.
+pub struct CardPlugin {
+    /// The name the card type is written as within a deck
+    pub name: String,
+    engine: Engine,
+    ast: AST,
+}
+
+impl CardPlugin {
+    /// Compile a card type from its script.
+    pub fn from_script(name: &str, script: &str) -> Result<Self, PluginError> {
+        let name = name.to_lowercase();
+
+        // A deck's `options` section is written the same way as a card type
+        if Card::is_built_in(&name) || name == "options" {
+            return Err(PluginError::ReservedName(name));
+        }
+
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        // Printing would draw over the terminal interface
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+
+        let ast = engine
+            .compile(script)
+            .map_err(|err| PluginError::InvalidScript(name.clone(), err.to_string()))?;
+
+        let plugin = Self { name, engine, ast };
+        if !plugin.defines("validate", 2) {
+            return Err(PluginError::MissingFunction(plugin.name, "validate"));
+        }
+
+        Ok(plugin)
+    }
+
+    /// Read a card type from a script, named after the script's file.
+    pub fn load(path: &Path) -> Result<Self, PluginError> {
+        let script = fs::read_to_string(path)
+            .map_err(|err| PluginError::Unreadable(path.display().to_string(), err.to_string()))?;
+        let name = path
+            .file_stem()
+            .map_or_else(String::new, |stem| stem.to_string_lossy().to_string());
+
+        CardPlugin::from_script(&name, &script)
+    }
+
+    /// Check if the script defines a function taking this many parameters.
+    fn defines(&self, function: &str, params: usize) -> bool {
+        self.ast
+            .iter_functions()
+            .any(|f| f.name == function && f.params.len() == params)
+    }
+
+    fn call(&self, function: &str, args: impl FuncArgs) -> Result<Dynamic, String> {
+        self.engine
+            .call_fn(&mut Scope::new(), &self.ast, function, args)
+            .map_err(|err| err.to_string())
+    }
+
+    /// Call a function expected to return a string.
+    fn call_string(&self, function: &str, args: impl FuncArgs) -> Result<String, String> {
+        self.call(function, args)?
+            .into_string()
+            .map_err(|found| format!("`{}` returned {} instead of a string", function, found))
+    }
+
+    /// Turn a card's question and content into the value passed to the rest of
+    /// the script.
+    pub fn parse(&self, question: &str, content: &str) -> Result<Dynamic, String> {
+        if !self.defines("parse", 2) {
+            return Ok(Dynamic::from(content.to_string()));
+        }
+
+        self.call("parse", (question.to_string(), content.to_string()))
+    }
+
+    /// Check if the typed answer is correct.
+    pub fn validate(&self, card: &Dynamic, input: &str) -> Result<bool, String> {
+        self.call("validate", (card.clone(), input.to_string()))?
+            .as_bool()
+            .map_err(|found| format!("`validate` returned {} instead of a bool", found))
+    }
+
+    /// The text shown above the answer.
+    pub fn render(&self, card: &Dynamic) -> Result<String, String> {
+        if !self.defines("render", 1) {
+            return Ok(String::new());
+        }
+
+        self.call_string("render", (card.clone(),))
+    }
+
+    /// The answer revealed after answering incorrectly, if the script gives
+    /// one.
+    pub fn answer(&self, card: &Dynamic) -> Result<Option<String>, String> {
+        if !self.defines("answer", 1) {
+            return Ok(None);
+        }
+
+        self.call_string("answer", (card.clone(),)).map(Some)
+    }
+
+    /// Instructions replacing the card's defaults, if the script gives them.
+    pub fn instructions(&self) -> Option<String> {
+        if !self.defines("instructions", 0) {
+            return None;
+        }
+
+        self.call_string("instructions", ()).ok()
+    }
+}
+
+/// Register a card type so decks can use it, replacing any plugin of the same
+/// name.
+pub fn register(plugin: CardPlugin) {
+    let mut plugins = PLUGINS.write().unwrap_or_else(|err| err.into_inner());
+
+    plugins.retain(|registered| registered.name != plugin.name);
+    plugins.push(Arc::new(plugin));
+}
+
+/// The registered card type written as `name`.
+pub(crate) fn find(name: &str) -> Option<Arc<CardPlugin>> {
+    PLUGINS
+        .read()
+        .unwrap_or_else(|err| err.into_inner())
+        .iter()
+        .find(|plugin| plugin.name == name)
+        .cloned()
+}
+
+/// Register every `.rhai` script within the directory, returning the names of
+/// the card types along with the errors of any scripts that couldn't be
+/// loaded, which are skipped. A missing directory has no plugins.
+pub fn load_dir(dir: &Path) -> Result<(Vec<String>, Vec<PluginError>), PluginError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok((Vec::new(), Vec::new())),
+        Err(err) => {
+            return Err(PluginError::Unreadable(
+                dir.display().to_string(),
+                err.to_string(),
+            ))
+        }
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == "rhai")
+        })
+        .collect();
+    paths.sort();
+
+    let mut names = Vec::new();
+    let mut errors = Vec::new();
+    for path in paths {
+        match CardPlugin::load(&path) {
+            Ok(plugin) => {
+                names.push(plugin.name.clone());
+                register(plugin);
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+
+    Ok((names, errors))
+}
@@ -1,7 +1,9 @@
+#[cfg(feature = "tui")]
 use tui::widgets::ListState;
 
+#[derive(Clone)]
 pub struct StatefulList<T> {
-    pub state: ListState,
+    selection: Option<usize>,
     pub items: Vec<T>,
 }
 
@@ -9,7 +11,7 @@ impl<T> StatefulList<T> {
     /// Create a StatefulList with the items passed in.
     pub fn with_items(items: Vec<T>) -> Self {
         let mut stateful_list = Self {
-            state: ListState::default(),
+            selection: None,
             items,
         };
 
@@ -22,7 +24,7 @@ impl<T> StatefulList<T> {
     /// Move the internally selected item forward.
     pub fn next(&mut self) {
         if !self.items.is_empty() {
-            let i = match self.state.selected() {
+            let i = match self.selection {
                 Some(i) => {
                     if i >= self.items.len() - 1 {
                         i
@@ -33,14 +35,14 @@ impl<T> StatefulList<T> {
                 None => 0,
             };
 
-            self.state.select(Some(i))
+            self.selection = Some(i);
         }
     }
 
     /// Move the internally selected item backwards.
     pub fn previous(&mut self) {
         if !self.items.is_empty() {
-            let i = match self.state.selected() {
+            let i = match self.selection {
                 Some(i) => {
                     if i == 0 {
                         i
@@ -51,7 +53,14 @@ impl<T> StatefulList<T> {
                 None => 0,
             };
 
-            self.state.select(Some(i))
+            self.selection = Some(i);
+        }
+    }
+
+    /// Select an item by its index, clamped to the last item.
+    pub fn select(&mut self, index: usize) {
+        if !self.items.is_empty() {
+            self.selection = Some(index.min(self.items.len() - 1));
         }
     }
 
@@ -66,7 +75,7 @@ impl<T> StatefulList<T> {
             return None;
         }
 
-        self.state.selected()
+        self.selection
     }
 
     /// Return the selected items value.
@@ -80,4 +89,12 @@ impl<T> StatefulList<T> {
             None => None,
         }
     }
+
+    /// The selection as drawn by a list widget.
+    #[cfg(feature = "tui")]
+    pub fn list_state(&self) -> ListState {
+        let mut state = ListState::default();
+        state.select(self.selected());
+        state
+    }
 }
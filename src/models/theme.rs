@@ -0,0 +1,206 @@
+use std::env;
+
+use serde::Deserialize;
+use tui::style::{Color, Modifier, Style};
+
+/// What a piece of content represents, used to pick its style.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Highlight {
+    Correct,
+    Incorrect,
+    /// Selected by the user but not yet validated
+    Selected,
+    Text,
+}
+
+impl Highlight {
+    /// Symbol shown alongside the content so correctness isn't conveyed by
+    /// colour alone.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Highlight::Correct => "✓ ",
+            Highlight::Incorrect => "✗ ",
+            Highlight::Selected | Highlight::Text => "",
+        }
+    }
+}
+
+/// The colours used to draw the application.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(from = "ThemeConfig")]
+pub struct Theme {
+    pub correct: Style,
+    pub incorrect: Style,
+    pub selected: Style,
+    pub text: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::dark()
+    }
+}
+
+impl Theme {
+    pub fn dark() -> Self {
+        Self {
+            correct: Style::default().fg(Color::Green),
+            incorrect: Style::default().fg(Color::Red),
+            selected: Style::default().fg(Color::Blue),
+            text: Style::default().fg(Color::White),
+        }
+    }
+
+    pub fn light() -> Self {
+        Self {
+            correct: Style::default().fg(Color::Rgb(0, 128, 0)),
+            incorrect: Style::default().fg(Color::Rgb(192, 0, 0)),
+            selected: Style::default().fg(Color::Rgb(0, 0, 192)),
+            text: Style::default().fg(Color::Black),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        Self {
+            correct: Style::default()
+                .fg(Color::LightGreen)
+                .add_modifier(Modifier::BOLD),
+            incorrect: Style::default()
+                .fg(Color::LightRed)
+                .add_modifier(Modifier::BOLD),
+            selected: Style::default()
+                .fg(Color::Black)
+                .bg(Color::LightYellow)
+                .add_modifier(Modifier::BOLD),
+            text: Style::default().fg(Color::White),
+        }
+    }
+
+    /// Based on the Okabe-Ito palette, which stays distinguishable with the
+    /// common forms of colour blindness.
+    pub fn colour_blind() -> Self {
+        Self {
+            correct: Style::default().fg(Color::Rgb(0, 114, 178)),
+            incorrect: Style::default().fg(Color::Rgb(230, 159, 0)),
+            selected: Style::default().fg(Color::Rgb(204, 121, 167)),
+            text: Style::default().fg(Color::White),
+        }
+    }
+
+    /// No colours at all, with selections shown using text attributes instead.
+    pub fn monochrome() -> Self {
+        Self {
+            correct: Style::default(),
+            incorrect: Style::default().add_modifier(Modifier::UNDERLINED),
+            selected: Style::default().add_modifier(Modifier::REVERSED),
+            text: Style::default(),
+        }
+    }
+
+    pub fn style(&self, highlight: Highlight) -> Style {
+        match highlight {
+            Highlight::Correct => self.correct,
+            Highlight::Incorrect => self.incorrect,
+            Highlight::Selected => self.selected,
+            Highlight::Text => self.text,
+        }
+    }
+
+    /// Check if colours have been disabled, see <https://no-color.org>.
+    pub fn no_color() -> bool {
+        env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum ThemeName {
+    #[default]
+    Dark,
+    Light,
+    HighContrast,
+    #[serde(alias = "color-blind")]
+    ColourBlind,
+    Monochrome,
+}
+
+/// A colour written within the config file, e.g. `green` or `#00ff00`.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(try_from = "String")]
+pub struct Colour(Color);
+
+impl TryFrom<String> for Colour {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let colour = match value.to_lowercase().replace(['-', '_'], "").as_str() {
+            "reset" => Color::Reset,
+            "black" => Color::Black,
+            "red" => Color::Red,
+            "green" => Color::Green,
+            "yellow" => Color::Yellow,
+            "blue" => Color::Blue,
+            "magenta" => Color::Magenta,
+            "cyan" => Color::Cyan,
+            "gray" | "grey" => Color::Gray,
+            "darkgray" | "darkgrey" => Color::DarkGray,
+            "lightred" => Color::LightRed,
+            "lightgreen" => Color::LightGreen,
+            "lightyellow" => Color::LightYellow,
+            "lightblue" => Color::LightBlue,
+            "lightmagenta" => Color::LightMagenta,
+            "lightcyan" => Color::LightCyan,
+            "white" => Color::White,
+            hex => match hex.strip_prefix('#') {
+                Some(hex) if hex.len() == 6 => {
+                    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16);
+
+                    match (channel(0), channel(2), channel(4)) {
+                        (Ok(r), Ok(g), Ok(b)) => Color::Rgb(r, g, b),
+                        _ => return Err(format!("Invalid colour `{}`", value)),
+                    }
+                }
+                _ => return Err(format!("Invalid colour `{}`", value)),
+            },
+        };
+
+        Ok(Colour(colour))
+    }
+}
+
+/// The `[theme]` table of the config file: a built-in theme with any of its
+/// colours overridden.
+#[derive(Deserialize, Default, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub name: ThemeName,
+    pub correct: Option<Colour>,
+    pub incorrect: Option<Colour>,
+    pub selected: Option<Colour>,
+    pub text: Option<Colour>,
+}
+
+impl From<ThemeConfig> for Theme {
+    fn from(config: ThemeConfig) -> Self {
+        let mut theme = match config.name {
+            ThemeName::Dark => Theme::dark(),
+            ThemeName::Light => Theme::light(),
+            ThemeName::HighContrast => Theme::high_contrast(),
+            ThemeName::ColourBlind => Theme::colour_blind(),
+            ThemeName::Monochrome => Theme::monochrome(),
+        };
+
+        for (style, colour) in [
+            (&mut theme.correct, config.correct),
+            (&mut theme.incorrect, config.incorrect),
+            (&mut theme.selected, config.selected),
+            (&mut theme.text, config.text),
+        ] {
+            if let Some(Colour(colour)) = colour {
+                *style = style.fg(colour);
+            }
+        }
+
+        theme
+    }
+}
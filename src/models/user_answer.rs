@@ -1,4 +1,6 @@
-#[derive(PartialEq, Eq, Copy, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum UserAnswer {
     Incorrect,
     Correct,
@@ -0,0 +1,27 @@
+use std::io;
+use std::path::PathBuf;
+
+/// Directory oxycards stores its data within, e.g. `~/.local/share/oxycards`.
+pub fn data_dir() -> Result<PathBuf, io::Error> {
+    dirs::data_dir()
+        .map(|dir| dir.join("oxycards"))
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Unable to locate a data directory"))
+}
+
+/// Directory oxycards reads its configuration from, e.g. `~/.config/oxycards`.
+pub fn config_dir() -> Result<PathBuf, io::Error> {
+    dirs::config_dir()
+        .map(|dir| dir.join("oxycards"))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "Unable to locate a config directory",
+            )
+        })
+}
+
+/// Directory card type plugins are loaded from, e.g.
+/// `~/.config/oxycards/card_types`.
+pub fn plugin_dir() -> Result<PathBuf, io::Error> {
+    Ok(config_dir()?.join("card_types"))
+}
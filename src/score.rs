@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// How many cards have been answered correctly and incorrectly.
+#[derive(Default, Clone, Serialize, Deserialize, Debug)]
+pub struct Score {
+    pub incorrect: usize,
+    pub correct: usize,
+}
+
+impl Score {
+    pub fn add_incorrect(&mut self) {
+        self.incorrect += 1;
+    }
+
+    pub fn add_correct(&mut self) {
+        self.correct += 1;
+    }
+}
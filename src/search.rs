@@ -0,0 +1,151 @@
+use regex::{Regex, RegexBuilder};
+
+use oxycards::Card;
+
+/// A search query, either plain text or a regex surrounded by slashes e.g.
+/// `/colou?r/`. Both are case-insensitive.
+pub enum Query {
+    Text(String),
+    Regex(Regex),
+}
+
+impl Query {
+    pub fn parse(query: &str) -> Result<Self, regex::Error> {
+        match query
+            .strip_prefix('/')
+            .and_then(|query| query.strip_suffix('/'))
+        {
+            Some(pattern) if !pattern.is_empty() => Ok(Query::Regex(
+                RegexBuilder::new(pattern).case_insensitive(true).build()?,
+            )),
+            _ => Ok(Query::Text(query.to_lowercase())),
+        }
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            Query::Text(query) => text.to_lowercase().contains(query),
+            Query::Regex(regex) => regex.is_match(text),
+        }
+    }
+
+    /// Check if the cards question, answers or choices match the query.
+    pub fn matches_card(&self, card: &Card) -> bool {
+        card.as_card_type()
+            .search_text()
+            .iter()
+            .any(|text| self.is_match(text))
+    }
+}
+
+/// The state of searching within the deck.
+#[derive(Default)]
+pub struct Search {
+    /// The query being typed, `None` while the search bar is closed
+    pub input: Option<String>,
+    /// The last query searched for
+    pub query: String,
+    /// Indexes of the cards matching the last query
+    pub matches: Vec<usize>,
+    pub error: Option<String>,
+}
+
+impl Search {
+    /// Open the search bar.
+    pub fn open(&mut self) {
+        self.input = Some(String::new());
+    }
+
+    /// Close the search bar without searching.
+    pub fn close(&mut self) {
+        self.input = None;
+    }
+
+    /// Search the cards for the query typed, closing the search bar.
+    pub fn submit(&mut self, cards: &[Card]) {
+        let query = self.input.take().unwrap_or_default();
+
+        self.matches.clear();
+        self.error = None;
+
+        match Query::parse(&query) {
+            Ok(parsed) => {
+                self.matches = cards
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, card)| parsed.matches_card(card))
+                    .map(|(i, _)| i)
+                    .collect();
+            }
+            Err(_) => self.error = Some(String::from("Invalid regex")),
+        }
+
+        self.query = query;
+    }
+
+    /// Keep the matches in step with a copy of the card at `original` being
+    /// inserted at `position`, as cramming does with incorrect answers.
+    pub fn insert_copy(&mut self, original: usize, position: usize) {
+        let matched = self.matches.contains(&original);
+
+        for index in self.matches.iter_mut().filter(|i| **i >= position) {
+            *index += 1;
+        }
+
+        if matched {
+            let at = self.matches.partition_point(|i| *i < position);
+            self.matches.insert(at, position);
+        }
+    }
+
+    /// The first match at or after the current card, wrapping around to the
+    /// start.
+    pub fn nearest_match(&self, current: usize) -> Option<usize> {
+        self.matches
+            .iter()
+            .find(|i| **i >= current)
+            .or(self.matches.first())
+            .copied()
+    }
+
+    /// The first match after the current card, wrapping around to the start.
+    pub fn next_match(&self, current: usize) -> Option<usize> {
+        self.matches
+            .iter()
+            .find(|i| **i > current)
+            .or(self.matches.first())
+            .copied()
+    }
+
+    /// The first match before the current card, wrapping around to the end.
+    pub fn previous_match(&self, current: usize) -> Option<usize> {
+        self.matches
+            .iter()
+            .rev()
+            .find(|i| **i < current)
+            .or(self.matches.last())
+            .copied()
+    }
+
+    /// Describe the results of the last search, e.g. `"chicken": 2/5`.
+    pub fn status(&self, current: usize) -> Option<String> {
+        if self.query.is_empty() {
+            return None;
+        }
+
+        if let Some(err) = &self.error {
+            return Some(format!("\"{}\": {}", self.query, err));
+        }
+
+        Some(match self.matches.iter().position(|i| *i == current) {
+            _ if self.matches.is_empty() => format!("\"{}\": No matches", self.query),
+            Some(position) => format!(
+                "\"{}\": {}/{}",
+                self.query,
+                position + 1,
+                self.matches.len()
+            ),
+            None => format!("\"{}\": {} matches", self.query, self.matches.len()),
+        })
+    }
+}
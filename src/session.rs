@@ -0,0 +1,85 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use oxycards::{Card, CardState};
+
+use crate::{history::History, paths, score::Score};
+
+/// A study session that was quit before every card was answered.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SavedSession {
+    /// SHA-256 hash of the decks content, used to discard sessions for edited
+    /// decks
+    pub deck_hash: String,
+    pub started_at: DateTime<Local>,
+    /// Time spent within the session so far, in seconds
+    pub elapsed: u64,
+    pub current_card: usize,
+    pub score: Score,
+    pub cards: Vec<CardState>,
+}
+
+impl SavedSession {
+    /// Location of the saved session for a deck within the users data directory.
+    pub fn path(deck: &Path) -> Result<PathBuf, io::Error> {
+        Ok(paths::data_dir()?.join("sessions").join(format!(
+            "{}.json",
+            SavedSession::hash_content(&History::deck_key(deck))
+        )))
+    }
+
+    /// Hash the content of a deck. The hash is written to disk, so it must stay
+    /// the same across builds and platforms.
+    pub fn hash_content(content: &str) -> String {
+        Sha256::digest(content.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    pub fn save(&self, deck: &Path) -> Result<(), io::Error> {
+        let path = SavedSession::path(deck)?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string(self)?)
+    }
+
+    /// Load the saved session for a deck, if there is one.
+    pub fn load(deck: &Path) -> Result<Option<Self>, io::Error> {
+        match fs::read_to_string(SavedSession::path(deck)?) {
+            Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Remove the saved session for a deck, if there is one.
+    pub fn remove(deck: &Path) -> Result<(), io::Error> {
+        match fs::remove_file(SavedSession::path(deck)?) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Apply the saved progress onto freshly parsed cards.
+    ///
+    /// Returns `false` if the session does not belong to the cards.
+    pub fn restore(&mut self, cards: &mut [Card]) -> bool {
+        if self.cards.len() != cards.len() || self.current_card >= cards.len() {
+            return false;
+        }
+
+        std::mem::take(&mut self.cards)
+            .into_iter()
+            .zip(cards.iter_mut())
+            .all(|(state, card)| card.as_card_type_mut().restore(state))
+    }
+}
@@ -1,24 +1,83 @@
 use tui::{
     backend::Backend,
+    buffer::Buffer,
     layout::{Alignment, Constraint, Direction, Layout, Rect},
-    style::{Color, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Widget, Wrap},
     Frame,
 };
 
-use crate::{
-    models::{
-        card::Card, card_types::fill_in_the_blanks::FillInTheBlanks, user_answer::UserAnswer,
-    },
-    AppState,
-};
+use oxycards::{Action, Card, CardView, Highlight, KeyBindings, KeyContext, UserAnswer};
+
+use crate::AppState;
+
+/// Every context, in the order they're listed within the help overlay.
+const HELP_CONTEXTS: &[KeyContext] = &[
+    KeyContext::Normal,
+    KeyContext::Validation,
+    KeyContext::Editing,
+    KeyContext::Search,
+    KeyContext::Popup,
+    KeyContext::Overview,
+    KeyContext::Help,
+];
 
 pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState) {
     let mut card_question = String::new();
-
-    let default_instructions =
-        "q: Quit application (unless specified otherwise), <ENTER>: Validate answer";
+    let mut awaiting_grade = false;
+
+    let keys = &app_state.config.keys;
+    let theme = app_state.config.theme.clone();
+    let context = app_state.key_context();
+    let editing = context == KeyContext::Editing;
+    let default_instructions = format!(
+        "{}: Quit application, {}: {}, {}: Help",
+        keys.describe(Action::Quit, context),
+        keys.describe(Action::Validate, context),
+        if app_state.submits_deck() {
+            "Submit answers"
+        } else {
+            "Validate answer"
+        },
+        keys.describe(Action::Help, context)
+    );
+    let validation_prompt = format!(
+        "Did you get this card correct? {}/{}",
+        keys.describe(Action::Correct, KeyContext::Validation),
+        keys.describe(Action::Incorrect, KeyContext::Validation)
+    );
+    let quit_prompt = format!(
+        "Are you sure you want to quit? {} {}/{}",
+        if app_state.resumable {
+            "Your progress will be saved and can be resumed later."
+        } else {
+            "This session can't be resumed later."
+        },
+        keys.describe(Action::Confirm, KeyContext::Popup),
+        keys.describe(Action::Cancel, KeyContext::Popup)
+    );
+    let submit_prompt = format!(
+        "Submit every answer for grading? {}/{} cards have been answered. {}/{}",
+        app_state
+            .cards
+            .items
+            .iter()
+            .filter(|card| card.as_card_type().has_progress())
+            .count(),
+        app_state.cards.items.len(),
+        keys.describe(Action::Confirm, KeyContext::Popup),
+        keys.describe(Action::Cancel, KeyContext::Popup)
+    );
+    let instructions = match app_state.cards.selected_value() {
+        Some(card) => card.as_card_type().instructions(&app_state.config.keys),
+        None => String::new(),
+    };
+    let search_instructions = format!(
+        "{}: Search, {}: Cancel",
+        keys.describe(Action::Confirm, KeyContext::Search),
+        keys.describe(Action::Cancel, KeyContext::Search)
+    );
 
     let size = f.size();
 
@@ -29,9 +88,12 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState) {
             .title(title.to_string())
     };
 
-    // A helper closure to create styled spans
-    let create_styled_span = |content: &str, colour: Color| -> Span {
-        Span::styled(content.to_string(), Style::default().fg(colour))
+    // A helper closure to create spans styled by what they represent
+    let create_styled_span = |content: &str, highlight: Highlight| -> Span {
+        Span::styled(
+            format!("{}{}", highlight.symbol(), content),
+            theme.style(highlight),
+        )
     };
 
     // The main canvas
@@ -62,166 +124,41 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState) {
     // Create card footer content
     let incorrect = Paragraph::new(create_styled_span(
         app_state.score.incorrect.to_string().as_ref(),
-        Color::Red,
+        Highlight::Incorrect,
     ))
     .alignment(Alignment::Left);
 
-    let cards = Paragraph::new(format!(
-        "{}/{}",
-        app_state
-            .cards
-            .selected()
-            .expect("This should never be None when this is called.")
-            + 1,
-        app_state.cards.items.len()
-    ))
+    let current = app_state
+        .cards
+        .selected()
+        .expect("This should never be None when this is called.");
+    let cards = Paragraph::new(match app_state.search.status(current) {
+        Some(status) => format!(
+            "{}/{} ({})",
+            current + 1,
+            app_state.cards.items.len(),
+            status
+        ),
+        None => format!("{}/{}", current + 1, app_state.cards.items.len()),
+    })
     .alignment(Alignment::Center);
 
-    let correct = Paragraph::new(Span::styled(
-        app_state.score.correct.to_string(),
-        Style::default().fg(Color::Green),
+    let correct = Paragraph::new(create_styled_span(
+        app_state.score.correct.to_string().as_ref(),
+        Highlight::Correct,
     ))
     .alignment(Alignment::Right);
 
-    if let Some(val) = app_state.cards.selected_value() {
-        match val {
-            Card::FlashCard(card) => {
-                card_question = card.question.clone();
-
-                let answer = Paragraph::new(if card.flipped {
-                    card.answer.to_string()
-                } else {
-                    String::new()
-                })
-                .block(create_block("Answer"))
-                .wrap(Wrap { trim: false })
-                .alignment(Alignment::Center);
-
-                if card.show_validation_popup {
-                    let area = centered_rect(60, 20, size);
-                    let paragraph = Paragraph::new("Did you get this card correct? y/n")
-                        .block(create_block("Validate"))
-                        .alignment(Alignment::Center);
-
-                    f.render_widget(Clear, area); //this clears out the background
-                    f.render_widget(paragraph, area);
-                }
-
-                f.render_widget(answer, card_layout[1]);
-            }
-            Card::MultipleChoice(card) => {
-                card_question = card.question.clone();
-                let choices: Vec<ListItem> = card
-                    .choices
-                    .items
-                    .iter()
-                    .map(|choice| {
-                        ListItem::new(create_styled_span(
-                            choice.content.as_ref(),
-                            match choice.selected {
-                                true => match card.user_answer {
-                                    UserAnswer::Correct => Color::Green,
-                                    UserAnswer::Incorrect => Color::Red,
-                                    UserAnswer::Undecided => Color::Blue,
-                                },
-                                false => match card.user_answer {
-                                    UserAnswer::Incorrect
-                                        if card.answers.contains(&choice.content) =>
-                                    {
-                                        Color::Green
-                                    }
-                                    _ => Color::White,
-                                },
-                            },
-                        ))
-                    })
-                    .collect();
-
-                let choices_list = List::new(choices)
-                    .block(create_block("Choices"))
-                    .highlight_symbol("> ");
-
-                f.render_stateful_widget(choices_list, card_layout[1], &mut card.choices.state);
-            }
-            Card::MultipleAnswer(card) => {
-                card_question = card.question.clone();
-
-                let choices: Vec<ListItem> = card
-                    .choices
-                    .items
-                    .iter()
-                    .map(|choice| match choice.selected {
-                        true => ListItem::new(create_styled_span(
-                            format!("[x] {}", choice.content).as_str(),
-                            match card.user_answer {
-                                UserAnswer::Correct => Color::Green,
-                                UserAnswer::Incorrect => Color::Red,
-                                UserAnswer::Undecided => Color::White,
-                            },
-                        )),
-                        false => ListItem::new(create_styled_span(
-                            format!("[ ] {}", choice.content).as_str(),
-                            match card.user_answer {
-                                UserAnswer::Correct if card.answers.contains(&choice.content) => {
-                                    Color::Green
-                                }
-                                _ => Color::White,
-                            },
-                        )),
-                    })
-                    .collect();
-
-                let choices_list = List::new(choices)
-                    .block(create_block("Choices"))
-                    .highlight_symbol("> ");
-
-                f.render_stateful_widget(choices_list, card_layout[1], &mut card.choices.state);
-            }
-            Card::FillInTheBlanks(card) => {
-                card_question = card.question.clone();
-
-                let content = Paragraph::new(match card.user_answer {
-                    UserAnswer::Undecided => vec![Spans::from(card.output.to_string())],
-                    _ => card.validated_output(),
-                })
-                .block(create_block("Content"))
-                .wrap(Wrap { trim: false })
-                .alignment(Alignment::Center);
-
-                f.render_widget(content, card_layout[1]);
-            }
-            Card::Order(card) => {
-                card_question = card.question.clone();
-
-                let choices: Vec<ListItem> = card
-                    .shuffled
-                    .items
-                    .iter()
-                    .enumerate()
-                    .map(|(i, choice)| match choice.selected {
-                        true => ListItem::new(Spans::from(vec![
-                            Span::raw(format!("{}. ", i + 1)),
-                            create_styled_span(format!("{}", choice.content).as_ref(), Color::Blue),
-                        ])),
-                        false => ListItem::new(Spans::from(vec![create_styled_span(
-                            format!("{}. {}", i + 1, choice.content).as_ref(),
-                            match card.user_answer {
-                                UserAnswer::Correct => Color::Green,
-                                UserAnswer::Incorrect => Color::Red,
-                                UserAnswer::Undecided => Color::White,
-                            },
-                        )])),
-                    })
-                    .collect();
-
-                let choices_list = List::new(choices)
-                    .block(create_block("Choices"))
-                    .highlight_symbol("> ");
-
-                f.render_stateful_widget(choices_list, card_layout[1], &mut card.shuffled.state);
-            }
-        }
-    };
+    if let Some(card) = app_state.cards.selected_value() {
+        card_question = card.as_card_type().prompt().to_string();
+        awaiting_grade = card.as_card_type().awaiting_grade();
+
+        let view = CardView {
+            theme: &theme,
+            editing,
+        };
+        f.render_widget(CardWidget { card, view }, card_layout[1]);
+    }
 
     // Render card title
     f.render_widget(
@@ -232,15 +169,14 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState) {
         card_layout[0],
     );
 
-    let instructions = match app_state.cards.selected_value() {
-        Some(card) => card.instructions(),
-        None => String::new(),
+    // Render the search bar while a query is typed, otherwise the
+    // instructions from our card instance
+    let controls = match &app_state.search.input {
+        Some(input) => format!("/{}\n{}", input, search_instructions),
+        None => format!("{}\n{}", instructions, default_instructions),
     };
-
-    // Render instructions from our card instance
     f.render_widget(
-        Paragraph::new(format!("{}\n{}", instructions, default_instructions))
-            .alignment(Alignment::Left),
+        Paragraph::new(controls).alignment(Alignment::Left),
         chunks[2],
     );
 
@@ -248,6 +184,149 @@ pub fn ui<B: Backend>(f: &mut Frame<B>, app_state: &mut AppState) {
     f.render_widget(incorrect, inner_card_layout[1]);
     f.render_widget(cards, inner_card_layout[1]);
     f.render_widget(correct, inner_card_layout[1]);
+
+    if awaiting_grade {
+        let area = centered_rect(60, 20, size);
+        let paragraph = Paragraph::new(validation_prompt)
+            .block(create_block("Validate"))
+            .alignment(Alignment::Center);
+
+        f.render_widget(Clear, area); //this clears out the background
+        f.render_widget(paragraph, area);
+    }
+
+    if app_state.show_overview {
+        let area = centered_rect(80, 80, size);
+
+        let items: Vec<ListItem> = app_state
+            .cards
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, card)| {
+                let (highlight, status) = match card.as_card_type().user_answer() {
+                    UserAnswer::Correct => (Highlight::Correct, "Correct"),
+                    UserAnswer::Incorrect => (Highlight::Incorrect, "Incorrect"),
+                    UserAnswer::Undecided => (Highlight::Text, "Unanswered"),
+                };
+
+                ListItem::new(Spans::from(vec![
+                    Span::raw(format!("{:>4}. ", i + 1)),
+                    // Pad the symbol so unanswered cards stay aligned
+                    Span::styled(
+                        format!("{:<2}{:<12}", highlight.symbol().trim_end(), status),
+                        theme.style(highlight),
+                    ),
+                    Span::raw(preview(card.as_card_type().prompt(), 60)),
+                ]))
+            })
+            .collect();
+
+        let overview = List::new(items)
+            .block(create_block("Cards"))
+            .highlight_symbol("> ");
+
+        f.render_widget(Clear, area); //this clears out the background
+        f.render_stateful_widget(overview, area, &mut app_state.overview);
+    }
+
+    if app_state.show_help {
+        let area = centered_rect(70, 70, size);
+        let help = help_text(&app_state.config.keys);
+
+        // Stop scrolling once the last line is in view
+        let max_scroll = (help.len() as u16).saturating_sub(area.height.saturating_sub(2));
+        app_state.help_scroll = app_state.help_scroll.min(max_scroll);
+
+        let paragraph = Paragraph::new(help)
+            .block(create_block("Help"))
+            .wrap(Wrap { trim: false })
+            .scroll((app_state.help_scroll, 0));
+
+        f.render_widget(Clear, area); //this clears out the background
+        f.render_widget(paragraph, area);
+    }
+
+    if app_state.show_submit_popup {
+        let area = centered_rect(60, 20, size);
+        let paragraph = Paragraph::new(submit_prompt)
+            .block(create_block("Submit"))
+            .wrap(Wrap { trim: false })
+            .alignment(Alignment::Center);
+
+        f.render_widget(Clear, area); //this clears out the background
+        f.render_widget(paragraph, area);
+    }
+
+    if app_state.show_quit_popup {
+        let area = centered_rect(60, 20, size);
+        let paragraph = Paragraph::new(quit_prompt)
+            .block(create_block("Quit"))
+            .wrap(Wrap { trim: false })
+            .alignment(Alignment::Center);
+
+        f.render_widget(Clear, area); //this clears out the background
+        f.render_widget(paragraph, area);
+    }
+}
+
+/// Draws a card's answer area.
+struct CardWidget<'a> {
+    card: &'a mut Card,
+    view: CardView<'a>,
+}
+
+impl Widget for CardWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.card.as_card_type_mut().render(area, buf, &self.view);
+    }
+}
+
+/// Shorten text to a single line of at most `width` characters.
+fn preview(text: &str, width: usize) -> String {
+    let line = text.lines().next().unwrap_or_default();
+
+    if line.chars().count() > width {
+        format!("{}…", line.chars().take(width - 1).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+/// Every keybinding, grouped by where it can be used.
+fn help_text(keys: &KeyBindings) -> Vec<Spans<'static>> {
+    let mut lines = vec![];
+
+    for context in HELP_CONTEXTS {
+        if !lines.is_empty() {
+            lines.push(Spans::default());
+        }
+
+        lines.push(Spans::from(Span::styled(
+            context.title(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )));
+
+        for action in context.actions() {
+            lines.push(Spans::from(format!(
+                "  {:<24}{}",
+                keys.describe(*action, *context),
+                action.description()
+            )));
+        }
+
+        let typed = match context {
+            KeyContext::Editing => Some("Add character pressed to the answer"),
+            KeyContext::Search => Some("Add character pressed to the search query"),
+            _ => None,
+        };
+
+        if let Some(description) = typed {
+            lines.push(Spans::from(format!("  {:<24}{}", "<Char>", description)));
+        }
+    }
+
+    lines
 }
 
 /// helper function to create a centered rect using up certain percentage of the available rect `r`
@@ -276,42 +355,3 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         )
         .split(popup_layout[1])[1]
 }
-
-impl FillInTheBlanks {
-    pub fn validated_output(&mut self) -> Vec<Spans> {
-        let new_content = self
-            .content
-            .split("__")
-            .take(self.answers.len())
-            .enumerate()
-            .map(|(index, item)| {
-                let user_content = match self.user_input.get(index) {
-                    Some(content) => content,
-                    None => "",
-                };
-
-                vec![
-                    Span::from(item),
-                    Span::styled(
-                        user_content,
-                        Style::default().fg(
-                            if self
-                                .answers
-                                .get(&index)
-                                .unwrap_or(&vec![])
-                                .contains(&user_content.to_string())
-                            {
-                                Color::Green
-                            } else {
-                                Color::Red
-                            },
-                        ),
-                    ),
-                ]
-            })
-            .flatten()
-            .collect::<Vec<Span>>();
-
-        vec![Spans::from(new_content)]
-    }
-}
@@ -0,0 +1,217 @@
+use oxycards::MatchOptions;
+use oxycards::{parse_deck, Card, CardType, FileType, ParsingError, UserAnswer};
+
+/// Every built-in card type, with every option each of them takes.
+const EVERY_CARD_TYPE: &str = "options trim
+
+---
+
+flashcard reversible
+
+# Capital of France
+Paris
+
+---
+
+multiple_choice
+
+# Largest planet
+- Mars
+* Jupiter
+- Venus
+
+---
+
+multiple_answer
+
+# Prime numbers
+[*] 2
+[ ] 4
+[*] 5
+
+---
+
+fill_in_the_blanks ignore_case fold_accents numeric
+
+# Fill in the gaps
+A _café|/caf[eé]/_ sells about _#3:1_ coffees a minute, written as _#1_.
+
+---
+
+order
+
+# Order the numbers
+1. one
+2. two
+3. three
+";
+
+/// Parse a deck, write it back out and parse it again, checking that both
+/// parses hold the same cards.
+fn round_trip(deck: &str) -> String {
+    let deck_matching = Card::deck_matching(deck).unwrap();
+    let cards = parse_deck(deck, FileType::Markdown).unwrap();
+
+    let written = Card::write_deck(&cards, &deck_matching).unwrap();
+    let reparsed = parse_deck(&written, FileType::Markdown).unwrap();
+
+    assert_eq!(cards.len(), reparsed.len());
+    for (card, reparsed) in cards.iter().zip(&reparsed) {
+        assert_eq!(card.as_card_type().name(), reparsed.as_card_type().name());
+        assert_eq!(
+            card.as_card_type().question(),
+            reparsed.as_card_type().question()
+        );
+        assert_eq!(
+            card.as_card_type().options(&MatchOptions::default()),
+            reparsed.as_card_type().options(&MatchOptions::default())
+        );
+        assert_eq!(card.to_markdown().unwrap(), reparsed.to_markdown().unwrap());
+    }
+
+    // Writing the deck again changes nothing
+    let rewritten = Card::write_deck(&reparsed, &Card::deck_matching(&written).unwrap()).unwrap();
+    assert_eq!(written, rewritten);
+
+    written
+}
+
+#[test]
+fn example_deck_round_trips() {
+    round_trip(include_str!("../example.md"));
+}
+
+#[test]
+fn every_card_type_and_option_round_trips() {
+    let written = round_trip(EVERY_CARD_TYPE);
+
+    assert!(written.starts_with("options trim\n"));
+    assert!(written.contains("flashcard reversible\n"));
+    assert!(written.contains("fill_in_the_blanks ignore_case fold_accents numeric\n"));
+}
+
+#[test]
+fn duplicate_choices_are_rejected() {
+    for deck in [
+        "multiple_choice\n\n# Question\n* a\n- a",
+        "multiple_answer\n\n# Question\n[*] a\n[ ] a",
+    ] {
+        assert!(matches!(
+            parse_deck(deck, FileType::Markdown),
+            Err(ParsingError::DuplicateChoice(choice)) if choice == "a"
+        ));
+    }
+}
+
+#[test]
+fn answers_are_checked_in_the_order_of_the_choices() {
+    let deck = r#"{"cards": [
+        {"type": "multiple_answer", "question": "Q", "choices": ["a", "b", "c"], "answers": ["c", "a"]}
+    ]}"#;
+    let mut cards = parse_deck(deck, FileType::Json).unwrap();
+
+    let Card::MultipleAnswer(card) = &mut cards[0] else {
+        panic!("expected a multiple answer card");
+    };
+    card.choices.items[0].selected = true;
+    card.choices.items[2].selected = true;
+
+    assert_eq!(card.validate_answer(), UserAnswer::Correct);
+}
+
+#[test]
+fn answers_are_listed_once() {
+    for card_type in ["multiple_choice", "multiple_answer"] {
+        let deck = format!(
+            r#"{{"cards": [{{"type": "{}", "question": "Q", "choices": ["a", "b"], "answers": ["a", "a"]}}]}}"#,
+            card_type
+        );
+
+        assert!(matches!(
+            parse_deck(&deck, FileType::Json),
+            Err(ParsingError::DuplicateAnswer(answer)) if answer == "a"
+        ));
+    }
+}
+
+#[test]
+fn multiple_choice_cards_have_one_answer() {
+    for (deck, count) in [
+        ("multiple_choice\n\n# Question\n* a\n* b", 2),
+        ("multiple_choice\n\n# Question\n- a\n- b", 0),
+    ] {
+        assert!(matches!(
+            parse_deck(deck, FileType::Markdown),
+            Err(ParsingError::AnswerCount(found)) if found == count
+        ));
+    }
+}
+
+#[test]
+fn structured_content_is_kept_as_written() {
+    let deck = r#"{"cards": [{"type": "flashcard", "question": "Q", "answer": "a\n\n---\nb"}]}"#;
+    let cards = parse_deck(deck, FileType::Json).unwrap();
+
+    assert_eq!(cards[0].as_card_type().search_text()[1], "a\n\n---\nb");
+    assert!(matches!(
+        parse_deck(r#"{"cards": []}"#, FileType::Json),
+        Err(ParsingError::NoCards)
+    ));
+}
+
+#[test]
+fn json_decks_round_trip_through_markdown() {
+    let deck = r#"{"options": ["ignore_case"], "cards": [
+        {"type": "flashcard", "question": "Capital of France", "answer": "Paris\non the Seine", "reversible": true},
+        {"type": "multiple_choice", "question": "Pick one", "choices": ["a", "b"], "answers": ["b"]},
+        {"type": "multiple_answer", "question": "Pick two", "choices": ["a", "b", "c"], "answers": ["c", "a"]},
+        {"type": "fill_in_the_blanks", "question": "Fill", "content": "The _blue|/azure?/_ sky", "options": ["trim"]},
+        {"type": "order", "question": "Count", "answer": ["one", "two"]}
+    ]}"#;
+    let cards = parse_deck(deck, FileType::Json).unwrap();
+
+    let deck_matching = Card::deck_matching("options ignore_case").unwrap();
+    let written = Card::write_deck(&cards, &deck_matching).unwrap();
+    let reparsed = parse_deck(&written, FileType::Markdown).unwrap();
+
+    assert_eq!(cards.len(), reparsed.len());
+    for (card, reparsed) in cards.iter().zip(&reparsed) {
+        assert_eq!(card.to_markdown().unwrap(), reparsed.to_markdown().unwrap());
+    }
+    assert_eq!(written, round_trip(&written));
+}
+
+#[test]
+fn content_markdown_cant_hold_is_rejected_when_written() {
+    for card in [
+        r#"{"type": "flashcard", "question": "Q", "answer": "a\n\n---\nb"}"#,
+        r#"{"type": "flashcard", "question": "Q", "answer": "a\n\nb"}"#,
+        r#"{"type": "flashcard", "question": "Q\nR", "answer": "a"}"#,
+        r#"{"type": "multiple_choice", "question": "Q", "choices": ["a\nb", "c"], "answers": ["c"]}"#,
+        r#"{"type": "order", "question": "Q", "answer": ["a---b", "c"]}"#,
+    ] {
+        let cards = parse_deck(&format!(r#"{{"cards": [{}]}}"#, card), FileType::Json).unwrap();
+
+        assert!(matches!(
+            Card::write_deck(&cards, &MatchOptions::default()),
+            Err(ParsingError::Unwritable(_))
+        ));
+    }
+}
+
+#[test]
+fn spreadsheet_rows_are_kept_as_written() {
+    let deck =
+        "type,question,answer\nflashcard reversible,Q,\"a\n\n---\nb\"\nmultiple_choice,Pick,*x|y\n";
+    let cards = parse_deck(deck, FileType::Csv).unwrap();
+
+    assert_eq!(cards[0].as_card_type().search_text()[1], "a\n\n---\nb");
+    assert_eq!(
+        cards[0].as_card_type().options(&MatchOptions::default()),
+        ["reversible"]
+    );
+    assert!(matches!(
+        parse_deck("type,question,answer\n", FileType::Csv),
+        Err(ParsingError::NoCards)
+    ));
+}